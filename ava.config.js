@@ -6,6 +6,7 @@ const configuration = {
     TS_NODE_PROJECT: './examples/tsconfig.json',
   },
   timeout: '1m',
+  nodeArguments: ['--expose-gc'],
 }
 
 if (parseInt(process.versions.napi, 10) < 4) {
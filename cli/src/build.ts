@@ -219,9 +219,11 @@ async function findUp(dir = process.cwd()): Promise<string | null> {
 }
 
 interface TypeDef {
-  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface'
+  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface' | 'error'
   name: string
+  js_doc?: string
   def: string
+  extends?: string
 }
 
 async function processIntermediateTypeFile(source: string, target: string) {
@@ -236,31 +238,38 @@ async function processIntermediateTypeFile(source: string, target: string) {
     .map((line) => line.trim())
     .filter(Boolean)
   let dts = ''
-  const classes = new Map<string, string>()
+  const classes = new Map<
+    string,
+    { def: string; jsDoc: string; extends?: string }
+  >()
   const impls = new Map<string, string>()
 
   lines.forEach((line) => {
     const def = JSON.parse(line) as TypeDef
+    const jsDoc = def.js_doc ?? ''
 
     switch (def.kind) {
       case 'struct':
-        classes.set(def.name, def.def)
+        classes.set(def.name, { def: def.def, jsDoc, extends: def.extends })
         break
       case 'impl':
         impls.set(def.name, def.def)
         break
       case 'interface':
-        dts += `interface ${def.name} {\n${indentLines(def.def, 2)}\n}\n`
+        dts += `${jsDoc}export interface ${def.name} {\n`
+        dts += `${indentLines(def.def, 2)}\n}\n`
         break
       default:
-        dts += def.def + '\n'
+        dts += jsDoc + def.def + '\n'
     }
   })
 
-  for (const [name, classDef] of classes.entries()) {
+  for (const [name, { def: classDef, jsDoc, extends: parent }] of classes) {
     const implDef = impls.get(name)
+    const heritage = parent ? ` extends ${parent}` : ''
 
-    dts += `export class ${name} {\n${indentLines(classDef, 2)}`
+    dts += `${jsDoc}export class ${name}${heritage} {\n`
+    dts += indentLines(classDef, 2)
 
     if (implDef) {
       dts += `\n${indentLines(implDef, 2)}`
@@ -269,13 +278,31 @@ async function processIntermediateTypeFile(source: string, target: string) {
     dts += '\n}\n'
   }
 
+  if (dts.indexOf('ExternalObject<') > -1) {
+    dts = EXTERNAL_OBJECT_DEF + dts
+  }
+
   await unlinkAsync(source)
   await writeFileAsync(target, dts, 'utf8')
 }
 
+// opaque handle of `External<T>`, the brand is the name of the rust type `T`
+const EXTERNAL_OBJECT_DEF = `declare const externalObjectBrand: unique symbol
+export interface ExternalObject<T extends string> {
+  readonly [externalObjectBrand]: T
+}
+`
+
 function indentLines(input: string, spaces: number) {
   return input
     .split('\n')
-    .map((line) => ''.padEnd(spaces, ' ') + line.trim())
+    .map((line) => {
+      const trimmed = line.trim()
+      // keep the ` * ` lines of JSDoc aligned with `/**`
+      return (
+        ''.padEnd(spaces, ' ') +
+        (trimmed.startsWith('*') ? ` ${trimmed}` : trimmed)
+      )
+    })
     .join('\n')
 }
@@ -6,7 +6,7 @@ pub struct NapiFn {
   pub name: Ident,
   pub js_name: String,
   pub attrs: Vec<Attribute>,
-  pub args: Vec<NapiFnArgKind>,
+  pub args: Vec<NapiFnArg>,
   pub ret: Option<syn::Type>,
   pub is_ret_result: bool,
   pub is_async: bool,
@@ -15,6 +15,9 @@ pub struct NapiFn {
   pub vis: syn::Visibility,
   pub parent: Option<Ident>,
   pub strict: bool,
+  /// Panics are caught and thrown as JavaScript errors, unless `#[napi(no_catch_unwind)]` is set
+  pub catch_unwind: bool,
+  pub comments: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +27,17 @@ pub struct CallbackArg {
   pub ret: Option<syn::Type>,
 }
 
+#[derive(Debug, Clone)]
+pub struct NapiFnArg {
+  pub kind: NapiFnArgKind,
+  /// `#[napi(default = ...)]`, used when the argument is `undefined` or missing
+  pub default: Option<syn::Expr>,
+  /// `Rest<T>` or `#[napi(rest)]`, collects all the remaining arguments
+  pub rest: bool,
+  /// `#[napi(strict)]`, the argument is validated before it's converted
+  pub strict: bool,
+}
+
 #[derive(Debug, Clone)]
 pub enum NapiFnArgKind {
   PatType(Box<syn::PatType>),
@@ -54,6 +68,11 @@ pub struct NapiStruct {
   pub fields: Vec<NapiStructField>,
   pub is_tuple: bool,
   pub kind: NapiStructKind,
+  /// `#[napi(extends = Base)]`, the parent class
+  pub extends: Option<syn::Path>,
+  /// Panics in the constructor and field accessors are caught, unless `#[napi(no_catch_unwind)]` is set
+  pub catch_unwind: bool,
+  pub comments: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -61,6 +80,8 @@ pub enum NapiStructKind {
   None,
   Constructor,
   Object,
+  /// `#[napi(error)]`, a JavaScript class extending `Error`
+  Error,
 }
 
 #[derive(Debug, Clone)]
@@ -70,6 +91,9 @@ pub struct NapiStructField {
   pub ty: syn::Type,
   pub getter: bool,
   pub setter: bool,
+  /// `#[napi(default = ...)]`, used when the field is missing in the JavaScript object
+  pub default: Option<syn::Expr>,
+  pub comments: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -77,19 +101,55 @@ pub struct NapiImpl {
   pub name: Ident,
   pub js_name: String,
   pub items: Vec<NapiFn>,
+  /// `#[napi]` associated consts, exported as static readonly members of the class
+  pub consts: Vec<NapiConst>,
   pub task_output_type: Option<Type>,
 }
 
+#[derive(Debug, Clone)]
+pub struct NapiConst {
+  pub name: Ident,
+  pub js_name: String,
+  pub ty: Type,
+  pub comments: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct NapiEnum {
   pub name: Ident,
   pub js_name: String,
   pub variants: Vec<NapiEnumVariant>,
+  pub kind: NapiEnumKind,
+  pub comments: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NapiEnumKind {
+  /// C-like enum, variants are exported as numbers
+  Number,
+  /// `#[napi(string_enum)]`, variants are mapped to string literals
+  String,
+  /// Data-carrying enum, variants are mapped to objects discriminated by the `type` field
+  Object,
+}
+
+#[derive(Debug, Clone)]
+pub enum NapiEnumValue {
+  Number(i32),
+  String(String),
 }
 
 #[derive(Debug, Clone)]
 pub struct NapiEnumVariant {
   pub name: Ident,
-  pub val: i32,
+  pub val: NapiEnumValue,
+  pub fields: Vec<NapiEnumVariantField>,
   pub comments: Vec<String>,
 }
+
+#[derive(Debug, Clone)]
+pub struct NapiEnumVariantField {
+  pub name: syn::Member,
+  pub js_name: String,
+  pub ty: syn::Type,
+}
@@ -6,6 +6,8 @@ mod r#enum;
 mod r#fn;
 mod r#struct;
 
+pub(crate) use r#fn::InjectedArg;
+
 pub trait TryToTokens {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()>;
 
@@ -26,3 +28,43 @@ fn get_register_ident(name: &str) -> Ident {
   let new_name = format!("__napi_register__{}", name);
   Ident::new(&new_name, Span::call_site())
 }
+
+/// Run the generated `body` in `catch_panic`, so panics don't unwind across the FFI boundary
+fn wrap_catch_panic(catch_unwind: bool, body: TokenStream) -> TokenStream {
+  if catch_unwind {
+    quote! { catch_panic(|| { #body }) }
+  } else {
+    quote! { { #body } }
+  }
+}
+
+/// Extract `T` from `Option<T>`
+pub(crate) fn get_option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+  get_generic_inner_type(ty, &["Option"])
+}
+
+/// Extract `T` from `Rest<T>` or `Vec<T>`
+pub(crate) fn get_rest_elem_type(ty: &syn::Type) -> Option<&syn::Type> {
+  get_generic_inner_type(ty, &["Rest", "Vec"])
+}
+
+pub(crate) fn get_generic_inner_type<'a>(
+  ty: &'a syn::Type,
+  wrappers: &[&str],
+) -> Option<&'a syn::Type> {
+  if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+    if let Some(syn::PathSegment {
+      ident,
+      arguments: syn::PathArguments::AngleBracketed(arguments),
+    }) = path.segments.last()
+    {
+      if wrappers.iter().any(|wrapper| ident == wrapper) && arguments.args.len() == 1 {
+        if let Some(syn::GenericArgument::Type(inner)) = arguments.args.first() {
+          return Some(inner);
+        }
+      }
+    }
+  }
+
+  None
+}
@@ -1,12 +1,18 @@
-use proc_macro2::{Literal, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
-use crate::{codegen::get_register_ident, BindgenResult, NapiEnum, TryToTokens};
+use crate::{
+  codegen::{get_option_inner_type, get_register_ident},
+  BindgenResult, NapiEnum, NapiEnumKind, NapiEnumValue, TryToTokens,
+};
 
 impl TryToTokens for NapiEnum {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
-    let register = self.gen_module_register();
-    let napi_value_conversion = self.gen_napi_value_map_impl();
+    let (napi_value_conversion, register) = match self.kind {
+      NapiEnumKind::Number => (self.gen_napi_value_map_impl(), self.gen_module_register()),
+      NapiEnumKind::String => (self.gen_napi_value_string_map_impl(), quote! {}),
+      NapiEnumKind::Object => (self.gen_napi_value_object_map_impl(), quote! {}),
+    };
 
     (quote! {
       #napi_value_conversion
@@ -19,6 +25,29 @@ impl TryToTokens for NapiEnum {
 }
 
 impl NapiEnum {
+  fn gen_type_name_impl(&self, value_type: TokenStream) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+
+    quote! {
+      impl TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          #value_type
+        }
+      }
+
+      impl ValidateNapiValue for #name {
+        fn type_of() -> Vec<ValueType> {
+          vec![#value_type]
+        }
+      }
+    }
+  }
+
   fn gen_napi_value_map_impl(&self) -> TokenStream {
     let name = &self.name;
     let name_str = self.name.to_string();
@@ -26,11 +55,13 @@ impl NapiEnum {
     let mut to_napi_branches = vec![];
 
     self.variants.iter().for_each(|v| {
-      let val = Literal::i32_unsuffixed(v.val);
-      let v_name = &v.name;
+      if let NapiEnumValue::Number(val) = &v.val {
+        let val = Literal::i32_unsuffixed(*val);
+        let v_name = &v.name;
 
-      from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
-      to_napi_branches.push(quote! { #name::#v_name => #val });
+        from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
+        to_napi_branches.push(quote! { #name::#v_name => #val });
+      }
     });
 
     quote! {
@@ -87,6 +118,166 @@ impl NapiEnum {
     }
   }
 
+  fn gen_napi_value_string_map_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let type_name_impl = self.gen_type_name_impl(quote! { napi::ValueType::String });
+    let mut from_napi_branches = vec![];
+    let mut to_napi_branches = vec![];
+
+    self.variants.iter().for_each(|v| {
+      if let NapiEnumValue::String(val) = &v.val {
+        let v_name = &v.name;
+
+        from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
+        to_napi_branches.push(quote! { #name::#v_name => #val });
+      }
+    });
+
+    quote! {
+      #type_name_impl
+
+      impl FromNapiValue for #name {
+        unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+          let val = String::from_napi_value(env, napi_val).map_err(|e| {
+            error!(
+              e.status,
+              "Failed to convert napi value into enum `{}`. {}",
+              #name_str,
+              e,
+            )
+          })?;
+
+          match val.as_str() {
+            #(#from_napi_branches,)*
+            _ => {
+              Err(error!(
+                Status::InvalidArg,
+                "value `{}` does not match any variant of enum `{}`",
+                val,
+                #name_str
+              ))
+            }
+          }
+        }
+      }
+
+      impl ToNapiValue for #name {
+        unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+          let val = match val {
+            #(#to_napi_branches,)*
+          };
+
+          <&str as ToNapiValue>::to_napi_value(env, val)
+        }
+      }
+    }
+  }
+
+  fn gen_napi_value_object_map_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let type_name_impl = self.gen_type_name_impl(quote! { napi::ValueType::Object });
+    let mut from_napi_branches = vec![];
+    let mut to_napi_branches = vec![];
+
+    for v in self.variants.iter() {
+      let v_name = &v.name;
+      let tag = match &v.val {
+        NapiEnumValue::String(tag) => tag,
+        NapiEnumValue::Number(_) => continue,
+      };
+
+      let mut field_destructions = vec![];
+      let mut obj_field_getters = vec![];
+      let mut obj_field_setters = vec![];
+
+      for (i, field) in v.fields.iter().enumerate() {
+        let member = &field.name;
+        let field_js_name = &field.js_name;
+        let ty = &field.ty;
+        let arg = Ident::new(&format!("arg{}", i), Span::call_site());
+
+        field_destructions.push(quote! { #member: #arg });
+        // absent or `undefined` optional fields are `None`, like the ones in `#[napi(object)]`
+        if get_option_inner_type(ty).is_some() {
+          obj_field_getters.push(quote! {
+            let #arg: #ty = obj.get::<_, #ty>(#field_js_name)?.flatten();
+          });
+          obj_field_setters.push(quote! {
+            if let Some(#arg) = #arg {
+              obj.set(#field_js_name, #arg)?;
+            }
+          });
+        } else {
+          obj_field_getters.push(quote! {
+            let #arg: #ty = obj.get(#field_js_name)?.ok_or_else(|| error!(
+              Status::InvalidArg,
+              "Missing field `{}` of variant `{}` in enum `{}`",
+              #field_js_name,
+              #tag,
+              #name_str,
+            ))?;
+          });
+          obj_field_setters.push(quote! { obj.set(#field_js_name, #arg)?; });
+        }
+      }
+
+      from_napi_branches.push(quote! {
+        #tag => {
+          #(#obj_field_getters)*
+          Ok(#name::#v_name { #(#field_destructions),* })
+        }
+      });
+      to_napi_branches.push(quote! {
+        #name::#v_name { #(#field_destructions),* } => {
+          obj.set("type", #tag)?;
+          #(#obj_field_setters)*
+        }
+      });
+    }
+
+    quote! {
+      #type_name_impl
+
+      impl FromNapiValue for #name {
+        unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+          let obj = Object::from_napi_value(env, napi_val)?;
+          let tag: String = obj.get("type")?.ok_or_else(|| error!(
+            Status::InvalidArg,
+            "Missing field `type` in enum `{}`",
+            #name_str,
+          ))?;
+
+          match tag.as_str() {
+            #(#from_napi_branches,)*
+            _ => {
+              Err(error!(
+                Status::InvalidArg,
+                "type `{}` does not match any variant of enum `{}`",
+                tag,
+                #name_str
+              ))
+            }
+          }
+        }
+      }
+
+      impl ToNapiValue for #name {
+        unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+          let env_wrapper = Env::from(env);
+          let mut obj = env_wrapper.create_object()?;
+
+          match val {
+            #(#to_napi_branches,)*
+          };
+
+          Object::to_napi_value(env, obj)
+        }
+      }
+    }
+  }
+
   fn gen_module_register(&self) -> TokenStream {
     let name_str = self.name.to_string();
     let js_name_lit = Literal::string(&self.js_name);
@@ -96,7 +287,10 @@ impl NapiEnum {
 
     for variant in self.variants.iter() {
       let name_lit = Literal::string(&variant.name.to_string());
-      let val_lit = Literal::i32_unsuffixed(variant.val);
+      let val_lit = match &variant.val {
+        NapiEnumValue::Number(val) => Literal::i32_unsuffixed(*val),
+        NapiEnumValue::String(_) => continue,
+      };
 
       define_properties.push(quote! {
         {
@@ -2,10 +2,52 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident},
-  BindgenResult, CallbackArg, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
+  codegen::{
+    get_intermediate_ident, get_option_inner_type, get_register_ident, get_rest_elem_type,
+    wrap_catch_panic,
+  },
+  BindgenResult, CallbackArg, FnKind, FnSelf, NapiFn, NapiFnArg, NapiFnArgKind, TryToTokens,
 };
 
+/// Arguments provided by the callback info rather than the JavaScript arguments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum InjectedArg {
+  Env,
+  This,
+  NewTarget,
+}
+
+impl NapiFnArg {
+  pub(crate) fn injected(&self) -> Option<InjectedArg> {
+    let path = match &self.kind {
+      NapiFnArgKind::PatType(path) => path,
+      NapiFnArgKind::Callback(_) => return None,
+    };
+
+    match &*path.ty {
+      syn::Type::Path(syn::TypePath { qself: None, path }) => {
+        match path.segments.last()?.ident.to_string().as_str() {
+          "Env" => Some(InjectedArg::Env),
+          "This" => Some(InjectedArg::This),
+          "NewTarget" => Some(InjectedArg::NewTarget),
+          _ => None,
+        }
+      }
+      _ => None,
+    }
+  }
+
+  /// `Option<T>`, `#[napi(default = ...)]` and rest arguments could be omitted
+  pub(crate) fn is_optional(&self) -> bool {
+    match &self.kind {
+      NapiFnArgKind::PatType(path) => {
+        self.rest || self.default.is_some() || get_option_inner_type(&path.ty).is_some()
+      }
+      NapiFnArgKind::Callback(_) => false,
+    }
+  }
+}
+
 impl TryToTokens for NapiFn {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
     let name_str = self.name.to_string();
@@ -13,6 +55,10 @@ impl TryToTokens for NapiFn {
     let args_len = self.args.len();
 
     let (arg_conversions, arg_names) = self.gen_arg_conversions();
+    let required_argc = match self.required_arg_count() {
+      0 => quote! { None },
+      count => quote! { Some(#count) },
+    };
     let receiver = self.gen_fn_receiver();
     let receiver_ret_name = Ident::new("_ret", Span::call_site());
     let ret = self.gen_fn_return(&receiver_ret_name);
@@ -32,8 +78,13 @@ impl TryToTokens for NapiFn {
       } else {
         quote! { Ok(#receiver(#(#arg_names),*).await) }
       };
+      let fut = match self.abort_signal_arg() {
+        // the future is dropped when the signal is aborted
+        Some(signal) => quote! { #signal.clone().abortable(async move { #call }) },
+        None => quote! { async move { #call } },
+      };
       quote! {
-        execute_tokio_future(env, async { #call }, |env, #receiver_ret_name| {
+        execute_tokio_future(env, #fut, |env, #receiver_ret_name| {
           #ret
         })
       }
@@ -45,7 +96,18 @@ impl TryToTokens for NapiFn {
       && self.kind != FnKind::Factory
     {
       quote! { #native_call }
-    } else if self.kind == FnKind::Constructor {
+    } else {
+      quote! {
+        CallbackInfo::<#args_len>::new(env, cb, #required_argc).and_then(|mut cb| {
+          #(#arg_conversions)*
+          #native_call
+        })
+      }
+    };
+
+    let function_call = wrap_catch_panic(self.catch_unwind, function_call);
+
+    let skip_from_factory = if self.kind == FnKind::Constructor {
       quote! {
         let call_from_factory = ___CALL_FROM_FACTORY.load(std::sync::atomic::Ordering::Relaxed);
         // constructor function is called from class `factory`
@@ -53,18 +115,9 @@ impl TryToTokens for NapiFn {
         if call_from_factory {
           return std::ptr::null_mut();
         }
-        CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
-          #(#arg_conversions)*
-          #native_call
-        })
       }
     } else {
-      quote! {
-        CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
-          #(#arg_conversions)*
-          #native_call
-        })
-      }
+      quote! {}
     };
 
     (quote! {
@@ -76,6 +129,8 @@ impl TryToTokens for NapiFn {
         env: sys::napi_env,
         cb: sys::napi_callback_info
       ) -> sys::napi_value {
+        #skip_from_factory
+        let _borrow_scope = BorrowScope::new();
         unsafe {
           #function_call.unwrap_or_else(|e| {
             JsError::from(e).throw_into(env);
@@ -111,17 +166,44 @@ impl NapiFn {
     }
 
     let mut skipped_arg_count = 0;
-    self.args.iter().enumerate().for_each(|(i, arg)| {
-      let i = i - skipped_arg_count;
+    self.args.iter().enumerate().for_each(|(raw_index, arg)| {
+      let i = raw_index - skipped_arg_count;
       let ident = Ident::new(&format!("arg{}", i), Span::call_site());
 
-      match arg {
+      match &arg.kind {
         NapiFnArgKind::PatType(path) => {
-          if &path.ty.to_token_stream().to_string() == "Env" {
-            args.push(quote! { Env::from(env) });
+          if let Some(injected) = arg.injected() {
+            let ty = &path.ty;
+            let ident = Ident::new(&format!("injected_arg{}", raw_index), Span::call_site());
+            arg_conversions.push(match injected {
+              InjectedArg::Env => quote! { let #ident = Env::from(env); },
+              InjectedArg::This => quote! {
+                let #ident = <#ty as FromNapiValue>::from_napi_value(env, cb.this())?;
+              },
+              InjectedArg::NewTarget => quote! { let #ident = cb.new_target()?; },
+            });
+            args.push(quote! { #ident });
             skipped_arg_count += 1;
+          } else if arg.rest {
+            let ty = &path.ty;
+            let elem = get_rest_elem_type(ty);
+            arg_conversions.push(quote! {
+              let #ident = <#ty>::from(cb.rest::<#elem>(#i)?);
+            });
+            args.push(quote! { #ident });
           } else {
-            arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path));
+            let conversion = self.gen_ty_arg_conversion(i, path, self.strict || arg.strict);
+            let conversion = match &arg.default {
+              Some(default) => quote! {
+                if cb.is_undefined(#i)? {
+                  #default
+                } else {
+                  #conversion
+                }
+              },
+              None => conversion,
+            };
+            arg_conversions.push(quote! { let #ident = #conversion; });
             args.push(quote! { #ident });
           }
         }
@@ -135,12 +217,27 @@ impl NapiFn {
     (arg_conversions, args)
   }
 
-  fn gen_ty_arg_conversion(
-    &self,
-    arg_name: &Ident,
-    index: usize,
-    path: &syn::PatType,
-  ) -> TokenStream {
+  /// Ident of the `AbortSignal` argument, which cancels the future of `async fn`
+  fn abort_signal_arg(&self) -> Option<Ident> {
+    self
+      .args
+      .iter()
+      .filter(|arg| arg.injected().is_none())
+      .enumerate()
+      .find_map(|(i, arg)| match &arg.kind {
+        NapiFnArgKind::PatType(path) => match &*path.ty {
+          syn::Type::Path(syn::TypePath { qself: None, path })
+            if path.segments.last()?.ident == "AbortSignal" =>
+          {
+            Some(Ident::new(&format!("arg{}", i), Span::call_site()))
+          }
+          _ => None,
+        },
+        NapiFnArgKind::Callback(_) => None,
+      })
+  }
+
+  fn gen_ty_arg_conversion(&self, index: usize, path: &syn::PatType, strict: bool) -> TokenStream {
     let ty = &*path.ty;
     match ty {
       syn::Type::Reference(syn::TypeReference {
@@ -149,16 +246,16 @@ impl NapiFn {
         ..
       }) => {
         quote! {
-          let #arg_name = <#elem as FromNapiMutRef>::from_napi_mut_ref(env, cb.get_arg(#index))?;
+          <#elem as FromNapiMutRef>::from_napi_mut_ref(env, cb.get_arg(#index))?
         }
       }
       syn::Type::Reference(syn::TypeReference { elem, .. }) => {
         quote! {
-          let #arg_name = <#elem as FromNapiRef>::from_napi_ref(env, cb.get_arg(#index))?;
+          <#elem as FromNapiRef>::from_napi_ref(env, cb.get_arg(#index))?
         }
       }
       _ => {
-        let type_check = if self.strict {
+        let type_check = if strict {
           quote! {
             <#ty as ValidateNapiValue>::validate(env, cb.get_arg(#index))?;
           }
@@ -167,15 +264,28 @@ impl NapiFn {
         };
 
         quote! {
-          let #arg_name = {
+          {
             #type_check
             <#ty as FromNapiValue>::from_napi_value(env, cb.get_arg(#index))?
-          };
+          }
         }
       }
     }
   }
 
+  /// Count of the JavaScript arguments must be passed,
+  /// the trailing `Option<T>` and `#[napi(default = ...)]` arguments are optional
+  pub(crate) fn required_arg_count(&self) -> usize {
+    self
+      .args
+      .iter()
+      .filter(|arg| arg.injected().is_none())
+      .enumerate()
+      .filter(|(_, arg)| !arg.is_optional())
+      .last()
+      .map_or(0, |(i, _)| i + 1)
+  }
+
   fn gen_cb_arg_conversion(&self, arg_name: &Ident, index: usize, cb: &CallbackArg) -> TokenStream {
     let mut inputs = vec![];
     let mut arg_conversions = vec![];
@@ -206,7 +316,8 @@ impl NapiFn {
 
         let mut ret_ptr = std::ptr::null_mut();
 
-        check_status!(
+        check_pending_exception!(
+          env,
           sys::napi_call_function(
             env,
             cb.this(),
@@ -243,10 +354,16 @@ impl NapiFn {
     let js_name = &self.js_name;
 
     if let Some(ty) = &self.ret {
+      let instance = if self.is_ret_result {
+        quote! { #ret? }
+      } else {
+        quote! { #ret }
+      };
+
       if self.kind == FnKind::Constructor {
-        quote! { cb.construct(#js_name, #ret) }
+        quote! { cb.construct(#js_name, #instance) }
       } else if self.kind == FnKind::Factory {
-        quote! { cb.factory(#js_name, #ret) }
+        quote! { cb.factory(#js_name, #instance) }
       } else if self.is_ret_result {
         if self.is_async {
           quote! {
@@ -254,11 +371,12 @@ impl NapiFn {
           }
         } else {
           quote! {
-            if #ret.is_ok() {
-              <Result<#ty> as ToNapiValue>::to_napi_value(env, #ret)
-            } else {
-              JsError::from(#ret.unwrap_err()).throw_into(env);
-              Ok(std::ptr::null_mut())
+            match #ret {
+              Ok(value) => <#ty as ToNapiValue>::to_napi_value(env, value),
+              Err(err) => {
+                JsError::from(err).throw_into(env);
+                Ok(std::ptr::null_mut())
+              }
             }
           }
         }
@@ -4,7 +4,7 @@ use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident},
+  codegen::{get_intermediate_ident, get_option_inner_type, get_register_ident, wrap_catch_panic},
   BindgenResult, FnKind, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
 };
 
@@ -26,29 +26,13 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
 
     impl FromNapiRef for #name {
       unsafe fn from_napi_ref(env: sys::napi_env, napi_val: sys::napi_value) -> Result<&'static Self> {
-        let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
-
-        check_status!(
-          sys::napi_unwrap(env, napi_val, &mut wrapped_val),
-          "Failed to recover `{}` type from napi value",
-          #name_str,
-        )?;
-
-        Ok(&*(wrapped_val as *const #name))
+        borrow_wrapped::<#name>(env, napi_val)
       }
     }
 
     impl FromNapiMutRef for #name {
       unsafe fn from_napi_mut_ref(env: sys::napi_env, napi_val: sys::napi_value) -> Result<&'static mut Self> {
-        let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
-
-        check_status!(
-          sys::napi_unwrap(env, napi_val, &mut wrapped_val),
-          "Failed to recover `{}` type from napi value",
-          #name_str,
-        )?;
-
-        Ok(&mut *(wrapped_val as *mut #name))
+        borrow_wrapped_mut::<#name>(env, napi_val)
       }
     }
   }
@@ -56,6 +40,11 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
 
 impl TryToTokens for NapiStruct {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    if self.kind == NapiStructKind::Error {
+      self.gen_error_class().to_tokens(tokens);
+      return Ok(());
+    }
+
     let napi_value_map_impl = self.gen_napi_value_map_impl();
 
     let class_helper_mod = if self.kind == NapiStructKind::Object {
@@ -129,18 +118,23 @@ impl NapiStruct {
     } else {
       quote! { #name {#(#fields),*} }
     };
+    let construct = wrap_catch_panic(
+      self.catch_unwind,
+      quote! {
+        CallbackInfo::<#fields_len>::new(env, cb, None)
+          .and_then(|cb| unsafe { cb.construct(#js_name_str, #construct) })
+      },
+    );
 
     quote! {
       extern "C" fn constructor(
         env: sys::napi_env,
         cb: sys::napi_callback_info
       ) -> sys::napi_value {
-        CallbackInfo::<#fields_len>::new(env, cb, None)
-          .and_then(|cb| unsafe { cb.construct(#js_name_str, #construct) })
-          .unwrap_or_else(|e| {
-            unsafe { JsError::from(e).throw_into(env) };
-            std::ptr::null_mut::<sys::napi_value__>()
-          })
+        #construct.unwrap_or_else(|e| {
+          unsafe { JsError::from(e).throw_into(env) };
+          std::ptr::null_mut::<sys::napi_value__>()
+        })
       }
     }
   }
@@ -152,11 +146,38 @@ impl NapiStruct {
         gen_napi_value_map_impl(&self.name, self.gen_to_napi_value_ctor_impl())
       }
       NapiStructKind::Object => self.gen_to_napi_value_obj_impl(),
+      NapiStructKind::Error => quote! {},
+    }
+  }
+
+  fn gen_error_class(&self) -> TokenStream {
+    let name = &self.name;
+    let js_name_str = &self.js_name;
+    let register_name = get_register_ident(&self.name.to_string());
+
+    quote! {
+      impl From<#name> for ErrorKind {
+        fn from(_: #name) -> Self {
+          ErrorKind::Custom(#js_name_str)
+        }
+      }
+
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[ctor]
+      fn #register_name() {
+        unsafe fn cb(env: sys::napi_env) -> Result<sys::napi_value> {
+          create_error_class(env, #js_name_str)
+        }
+
+        register_module_export(#js_name_str, cb);
+      }
     }
   }
 
   fn gen_to_napi_value_ctor_impl(&self) -> TokenStream {
     let name = &self.name;
+    let name_str = self.name.to_string();
     let js_name_str = &self.js_name;
 
     let mut field_conversions = vec![];
@@ -190,7 +211,7 @@ impl NapiStruct {
     quote! {
       impl ToNapiValue for #name {
         unsafe fn to_napi_value(env: sys::napi_env, val: #name) -> Result<sys::napi_value> {
-          if let Some(ctor_ref) = get_class_constructor(#js_name_str) {
+          if let Some(ctor_ref) = get_class_constructor(env, #name_str) {
             let mut ctor = std::ptr::null_mut();
 
             check_status!(
@@ -229,19 +250,47 @@ impl NapiStruct {
     for field in self.fields.iter() {
       let field_js_name = &field.js_name;
       let ty = &field.ty;
+      let is_optional = get_option_inner_type(ty).is_some();
 
-      match &field.name {
+      let binding = match &field.name {
         syn::Member::Named(ident) => {
           field_destructions.push(quote! { #ident });
-          obj_field_setters.push(quote! { obj.set(#field_js_name, #ident)?; });
-          obj_field_getters.push(quote! { let #ident: #ty = obj.get(#field_js_name)?.expect(&format!("Field {} should exist", #field_js_name)); });
+          ident.clone()
         }
         syn::Member::Unnamed(i) => {
-          field_destructions.push(quote! { arg#i });
-          obj_field_setters.push(quote! { obj.set(#field_js_name, arg#1)?; });
-          obj_field_getters.push(quote! { let arg#i: #ty = obj.get(#field_js_name)?.expect(&format!("Field {} should exist", #field_js_name)); });
+          let arg = Ident::new(&format!("arg{}", i.index), Span::call_site());
+          field_destructions.push(quote! { #arg });
+          arg
         }
+      };
+
+      if is_optional {
+        obj_field_setters.push(quote! {
+          if let Some(#binding) = #binding {
+            obj.set(#field_js_name, #binding)?;
+          }
+        });
+      } else {
+        obj_field_setters.push(quote! { obj.set(#field_js_name, #binding)?; });
       }
+
+      let getter = match &field.default {
+        Some(default) => quote! {
+          let #binding: #ty = obj.get(#field_js_name)?.unwrap_or_else(|| #default);
+        },
+        None if is_optional => quote! {
+          let #binding: #ty = obj.get::<_, #ty>(#field_js_name)?.flatten();
+        },
+        None => quote! {
+          let #binding: #ty = obj.get(#field_js_name)?.ok_or_else(|| error!(
+            Status::InvalidArg,
+            "Missing field `{}` in struct `{}`",
+            #field_js_name,
+            #name_str,
+          ))?;
+        },
+      };
+      obj_field_getters.push(getter);
     }
 
     let destructed_fields = if self.is_tuple {
@@ -308,6 +357,17 @@ impl NapiStruct {
       let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
 
       if field.getter {
+        let get_field = wrap_catch_panic(
+          self.catch_unwind,
+          quote! {
+            CallbackInfo::<0>::new(env, cb, Some(0))
+              .and_then(|mut cb| unsafe { cb.unwrap_borrow::<#struct_name>() })
+              .and_then(|obj| {
+                let val = obj.#field_ident.to_owned();
+                unsafe { <#ty as ToNapiValue>::to_napi_value(env, val) }
+              })
+          },
+        );
         getters_setters.push((
           field.js_name.clone(),
           quote! {
@@ -315,22 +375,33 @@ impl NapiStruct {
               env: sys::napi_env,
               cb: sys::napi_callback_info
             ) -> sys::napi_value {
-              CallbackInfo::<0>::new(env, cb, Some(0))
-                .and_then(|mut cb| unsafe { cb.unwrap_borrow::<#struct_name>() })
-                .and_then(|obj| {
-                  let val = obj.#field_ident.to_owned();
-                  unsafe { <#ty as ToNapiValue>::to_napi_value(env, val) }
-                })
-                .unwrap_or_else(|e| {
-                  unsafe { JsError::from(e).throw_into(env) };
-                  std::ptr::null_mut::<sys::napi_value__>()
-                })
+              let _borrow_scope = BorrowScope::new();
+              #get_field.unwrap_or_else(|e| {
+                unsafe { JsError::from(e).throw_into(env) };
+                std::ptr::null_mut::<sys::napi_value__>()
+              })
             }
           },
         ));
       }
 
       if field.setter {
+        let set_field = wrap_catch_panic(
+          self.catch_unwind,
+          quote! {
+            CallbackInfo::<1>::new(env, cb, Some(1))
+              .and_then(|mut cb_info| unsafe {
+                cb_info.unwrap_borrow_mut::<#struct_name>()
+                  .and_then(|obj| {
+                    <#ty as FromNapiValue>::from_napi_value(env, cb_info.get_arg(0))
+                      .and_then(move |val| {
+                        obj.#field_ident = val;
+                        <() as ToNapiValue>::to_napi_value(env, ())
+                      })
+                  })
+              })
+          },
+        );
         getters_setters.push((
           field.js_name.clone(),
           quote! {
@@ -338,21 +409,11 @@ impl NapiStruct {
               env: sys::napi_env,
               cb: sys::napi_callback_info
             ) -> sys::napi_value {
-              CallbackInfo::<1>::new(env, cb, Some(1))
-                .and_then(|mut cb_info| unsafe {
-                  cb_info.unwrap_borrow_mut::<#struct_name>()
-                    .and_then(|obj| {
-                      <#ty as FromNapiValue>::from_napi_value(env, cb_info.get_arg(0))
-                        .and_then(move |val| {
-                          obj.#field_ident = val;
-                          <() as ToNapiValue>::to_napi_value(env, ())
-                        })
-                    })
-                })
-                .unwrap_or_else(|e| {
-                  unsafe { JsError::from(e).throw_into(env) };
-                  std::ptr::null_mut::<sys::napi_value__>()
-                })
+              let _borrow_scope = BorrowScope::new();
+              #set_field.unwrap_or_else(|e| {
+                unsafe { JsError::from(e).throw_into(env) };
+                std::ptr::null_mut::<sys::napi_value__>()
+              })
             }
           },
         ));
@@ -401,12 +462,18 @@ impl NapiStruct {
       props.push(prop);
     }
 
+    let register_parent = self.extends.as_ref().and_then(|parent| {
+      let parent_str = parent.segments.last()?.ident.to_string();
+      Some(quote! { register_class_parent(#name_str, #parent_str); })
+    });
+
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
       #[ctor]
       fn #struct_register_name() {
         register_class(#name_str, #js_name, vec![#(#props),*]);
+        #register_parent
       }
     }
   }
@@ -448,6 +515,12 @@ impl NapiImpl {
 
       let appendix = match item.kind {
         FnKind::Constructor => quote! { .with_ctor(#intermediate_name) },
+        FnKind::Getter if item.fn_self.is_none() => {
+          quote! { .with_getter(#intermediate_name).with_property_attributes(PropertyAttributes::Static) }
+        }
+        FnKind::Setter if item.fn_self.is_none() => {
+          quote! { .with_setter(#intermediate_name).with_property_attributes(PropertyAttributes::Static) }
+        }
         FnKind::Getter => quote! { .with_getter(#intermediate_name) },
         FnKind::Setter => quote! { .with_setter(#intermediate_name) },
         _ => {
@@ -462,6 +535,34 @@ impl NapiImpl {
       appendix.to_tokens(prop);
     }
 
+    let struct_name = &self.name;
+    for item in self.consts.iter() {
+      let name = &item.name;
+      let ty = &item.ty;
+      let intermediate_name = get_intermediate_ident(&name.to_string());
+
+      // consts are exported as static getters, the value is converted when it's accessed
+      methods.push(quote! {
+        #[doc(hidden)]
+        extern "C" fn #intermediate_name(
+          env: sys::napi_env,
+          _cb: sys::napi_callback_info
+        ) -> sys::napi_value {
+          catch_panic(|| unsafe { <#ty as ToNapiValue>::to_napi_value(env, #struct_name::#name) })
+            .unwrap_or_else(|e| {
+              unsafe { JsError::from(e).throw_into(env) };
+              std::ptr::null_mut::<sys::napi_value__>()
+            })
+        }
+      });
+      props.insert(&item.js_name, {
+        let js_name = Literal::string(&item.js_name);
+        quote! {
+          Property::new(#js_name).unwrap().with_getter(#intermediate_name).with_property_attributes(PropertyAttributes::Static)
+        }
+      });
+    }
+
     let mut props: Vec<_> = props.into_iter().collect();
     props.sort_by_key(|(_, prop)| prop.to_string());
     let props = props.into_iter().map(|(_, prop)| prop);
@@ -12,17 +12,67 @@ pub struct TypeDef {
   pub kind: String,
   pub name: String,
   pub def: String,
+  pub js_doc: String,
+  /// Parent class of `struct`
+  pub extends: Option<String>,
 }
 
 impl ToString for TypeDef {
   fn to_string(&self) -> String {
+    let extends = match &self.extends {
+      Some(parent) => format!(r#", "extends": "{}""#, parent),
+      None => String::new(),
+    };
+
     format!(
-      r#"{{"kind": "{}", "name": "{}", "def": "{}"}}"#,
-      self.kind, self.name, self.def,
+      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}}}"#,
+      self.kind, self.name, self.js_doc, self.def, extends,
     )
   }
 }
 
+/// Convert the Rust doc comments into JSDoc, lines are joined with escaped `\n`
+/// and a trailing `\n` is appended, so it could be prepended to the definition directly.
+pub fn js_doc_from_comments(comments: &[String]) -> String {
+  let mut lines = comments
+    .iter()
+    .flat_map(|c| c.split('\n'))
+    .map(|line| {
+      // `/// doc` is extracted as ` doc`
+      let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+      line
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\t', "  ")
+        .replace("*/", "*\\\\/")
+    })
+    .skip_while(|line| line.is_empty())
+    .collect::<Vec<_>>();
+
+  let len = lines
+    .iter()
+    .rposition(|line| !line.is_empty())
+    .map_or(0, |i| i + 1);
+  lines.truncate(len);
+
+  match lines.len() {
+    0 => String::new(),
+    1 => format!("/** {} */\\n", lines[0]),
+    _ => format!(
+      "/**\\n{}\\n */\\n",
+      lines
+        .iter()
+        .map(|line| if line.is_empty() {
+          " *".to_owned()
+        } else {
+          format!(" * {}", line)
+        })
+        .collect::<Vec<_>>()
+        .join("\\n")
+    ),
+  }
+}
+
 pub trait ToTypeDef {
   fn to_type_def(&self) -> TypeDef;
 }
@@ -42,8 +92,9 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("u64", "BigInt"),
     ("u128", "BigInt"),
     ("i128", "BigInt"),
-    ("usize", "BigInt"),
-    ("isize", "BigInt"),
+    ("usize", "number"),
+    ("isize", "number"),
+    ("f32", "number"),
     ("BigInt", "BigInt"),
     ("bool", "boolean"),
     ("String", "string"),
@@ -58,7 +109,22 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("ArrayBuffer", "ArrayBuffer"),
     ("DataView", "DataView"),
     ("Date", "Date"),
+    ("SystemTime", "Date"),
+    ("DateTime", "Date"),
+    ("NaiveDateTime", "Date"),
     ("Buffer", "Buffer"),
+    ("BufferRef", "Buffer"),
+    ("Int8Array", "Int8Array"),
+    ("Uint8Array", "Uint8Array"),
+    ("Uint8ClampedArray", "Uint8ClampedArray"),
+    ("Int16Array", "Int16Array"),
+    ("Uint16Array", "Uint16Array"),
+    ("Int32Array", "Int32Array"),
+    ("Uint32Array", "Uint32Array"),
+    ("Float32Array", "Float32Array"),
+    ("Float64Array", "Float64Array"),
+    ("BigInt64Array", "BigInt64Array"),
+    ("BigUint64Array", "BigUint64Array"),
     // TODO: Vec<u8> should be Buffer, now is Array<number>
     ("Vec", "Array<{}>"),
     ("Option", "{} | null"),
@@ -67,12 +133,12 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("Either3", "{} | {} | {}"),
     ("Either4", "{} | {} | {} | {}"),
     ("Either5", "{} | {} | {} | {} | {}"),
+    ("Promise", "Promise<{}>"),
     ("unknown", "unknown"),
     ("null", "null"),
     ("symbol", "symbol"),
     ("external", "object"),
     ("AbortSignal", "AbortSignal"),
-    ("Function", "(...args: any[]) => any"),
   ]);
 
   map
@@ -96,9 +162,79 @@ fn fill_ty(template: &str, args: Vec<String>) -> String {
   ret
 }
 
+// `&[T]` and `&mut [T]` borrow the backing store of the matching TypedArray
+fn slice_to_ts_type(elem: &Type) -> &'static str {
+  let elem_ty = match elem {
+    Type::Path(syn::TypePath { qself: None, path }) => path.get_ident().map(|i| i.to_string()),
+    _ => None,
+  };
+
+  match elem_ty.as_deref() {
+    Some("i8") => "Int8Array",
+    Some("u8") => "Uint8Array",
+    Some("i16") => "Int16Array",
+    Some("u16") => "Uint16Array",
+    Some("i32") => "Int32Array",
+    Some("u32") => "Uint32Array",
+    Some("f32") => "Float32Array",
+    Some("f64") => "Float64Array",
+    Some("i64") => "BigInt64Array",
+    Some("u64") => "BigUint64Array",
+    _ => "any",
+  }
+}
+
+// `External<Db>` is branded by the rust type name `Db`, so the handles of different types can't be mixed up
+fn external_brand(arguments: &syn::PathArguments) -> String {
+  if let syn::PathArguments::AngleBracketed(arguments) = arguments {
+    if let Some(syn::GenericArgument::Type(Type::Path(syn::TypePath { path, .. }))) =
+      arguments.args.first()
+    {
+      if let Some(segment) = path.segments.last() {
+        return segment.ident.to_string();
+      }
+    }
+  }
+
+  "unknown".to_owned()
+}
+
+// `Function<(A, B), R>` is called with the elements of the arguments tuple
+fn function_to_ts_type(arguments: &syn::PathArguments) -> String {
+  let mut generics = match arguments {
+    syn::PathArguments::AngleBracketed(arguments) => arguments
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        syn::GenericArgument::Type(generic_ty) => Some(generic_ty),
+        _ => None,
+      })
+      .collect::<Vec<_>>(),
+    _ => vec![],
+  }
+  .into_iter();
+
+  let args = match generics.next() {
+    Some(Type::Tuple(tuple)) => tuple
+      .elems
+      .iter()
+      .enumerate()
+      .map(|(i, arg)| format!("arg{}: {}", i, ty_to_ts_type(arg, false)))
+      .collect::<Vec<_>>(),
+    Some(arg) => vec![format!("arg0: {}", ty_to_ts_type(arg, false))],
+    None => vec![],
+  };
+  let ret = generics
+    .next()
+    .map_or_else(|| "unknown".to_owned(), |ret| ty_to_ts_type(ret, true));
+
+  format!("({}) => {}", args.join(", "), ret)
+}
+
 pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> String {
   match ty {
     Type::Reference(r) => ty_to_ts_type(&r.elem, is_return_ty),
+    Type::Slice(slice) => slice_to_ts_type(&slice.elem).to_owned(),
     Type::Tuple(tuple) => {
       if tuple.elems.is_empty() {
         "undefined".to_owned()
@@ -143,6 +279,18 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> String {
               Some("Promise<unknown>".to_owned())
             }
           });
+        } else if rust_ty == "External" {
+          ts_ty = Some(format!("ExternalObject<'{}'>", external_brand(arguments)));
+        } else if rust_ty == "Function" {
+          ts_ty = Some(function_to_ts_type(arguments));
+        } else if rust_ty == "ThreadsafeFunction" {
+          let value_ty = args.first().cloned().unwrap_or_else(|| "any".to_owned());
+          // `ErrorStrategy::Fatal` callbacks don't receive the leading `err` argument
+          ts_ty = if args.get(1).map(String::as_str) == Some("Fatal") {
+            Some(format!("(value: {}) => void", value_ty))
+          } else {
+            Some(format!("(err: Error | null, value: {}) => void", value_ty))
+          };
         } else if let Some(&known_ty) = KNOWN_TYPES.get(rust_ty.as_str()) {
           if known_ty.contains("{}") {
             ts_ty = Some(fill_ty(known_ty, args));
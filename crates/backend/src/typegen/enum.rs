@@ -1,16 +1,27 @@
-use super::{ToTypeDef, TypeDef};
-use crate::NapiEnum;
+use super::{js_doc_from_comments, ToTypeDef, TypeDef};
+use crate::{codegen::get_option_inner_type, ty_to_ts_type, NapiEnum, NapiEnumKind, NapiEnumValue};
 
 impl ToTypeDef for NapiEnum {
   fn to_type_def(&self) -> TypeDef {
-    TypeDef {
-      kind: "enum".to_owned(),
-      name: self.js_name.to_owned(),
-      def: format!(
+    let def = match self.kind {
+      NapiEnumKind::Number => format!(
         r"export enum {js_name} {{ {variants} }}",
         js_name = &self.js_name,
         variants = self.gen_ts_variants()
       ),
+      NapiEnumKind::String | NapiEnumKind::Object => format!(
+        r"export type {js_name} = {variants}",
+        js_name = &self.js_name,
+        variants = self.gen_ts_union()
+      ),
+    };
+
+    TypeDef {
+      kind: "enum".to_owned(),
+      name: self.js_name.to_owned(),
+      def,
+      js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
     }
   }
 }
@@ -20,8 +31,35 @@ impl NapiEnum {
     self
       .variants
       .iter()
-      .map(|v| format!("{} = {}", v.name, v.val))
+      .filter_map(|v| match &v.val {
+        NapiEnumValue::Number(val) => Some(format!("{} = {}", v.name, val)),
+        NapiEnumValue::String(_) => None,
+      })
       .collect::<Vec<_>>()
       .join(", ")
   }
+
+  fn gen_ts_union(&self) -> String {
+    self
+      .variants
+      .iter()
+      .filter_map(|v| match &v.val {
+        NapiEnumValue::String(val) if self.kind == NapiEnumKind::String => {
+          Some(format!("'{}'", val))
+        }
+        NapiEnumValue::String(val) => {
+          let fields = std::iter::once(format!("type: '{}'", val))
+            .chain(v.fields.iter().map(|f| match get_option_inner_type(&f.ty) {
+              Some(inner) => format!("{}?: {}", f.js_name, ty_to_ts_type(inner, false)),
+              None => format!("{}: {}", f.js_name, ty_to_ts_type(&f.ty, false)),
+            }))
+            .collect::<Vec<_>>()
+            .join(", ");
+          Some(format!("{{ {} }}", fields))
+        }
+        NapiEnumValue::Number(_) => None,
+      })
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
 }
@@ -1,8 +1,11 @@
 use convert_case::{Case, Casing};
 use quote::ToTokens;
 
-use super::{ty_to_ts_type, ToTypeDef, TypeDef};
-use crate::{CallbackArg, FnKind, NapiFn};
+use super::{js_doc_from_comments, ty_to_ts_type, ToTypeDef, TypeDef};
+use crate::{
+  codegen::{get_generic_inner_type, InjectedArg},
+  get_rest_elem_type, CallbackArg, FnKind, NapiFn, NapiFnArgKind,
+};
 
 impl ToTypeDef for NapiFn {
   fn to_type_def(&self) -> TypeDef {
@@ -18,10 +21,28 @@ impl ToTypeDef for NapiFn {
       kind: "fn".to_owned(),
       name: self.js_name.clone(),
       def,
+      js_doc: self.gen_js_doc(),
+      extends: None,
     }
   }
 }
 
+// Parse "`name` - description" item of the rustdoc `# Arguments` section
+fn parse_arg_doc(item: &str) -> Option<(&str, &str)> {
+  let (name, desc) = item.strip_prefix('`')?.split_once('`')?;
+  let desc = desc.trim_start().trim_start_matches(['-', ':']).trim();
+
+  Some((name, desc))
+}
+
+// `mut value` is named `value` in JavaScript
+fn arg_name(pat: &syn::Pat) -> String {
+  match pat {
+    syn::Pat::Ident(pat_ident) => pat_ident.ident.to_string().to_case(Case::Camel),
+    _ => pat.to_token_stream().to_string().to_case(Case::Camel),
+  }
+}
+
 fn gen_callback_type(callback: &CallbackArg) -> String {
   format!(
     "({args}) => {ret}",
@@ -40,29 +61,99 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
 }
 
 impl NapiFn {
-  fn gen_ts_func_args(&self) -> String {
-    self
-      .args
-      .iter()
-      .filter_map(|arg| match arg {
-        crate::NapiFnArgKind::PatType(path) => {
-          if path.ty.to_token_stream().to_string() == "Env" {
-            return None;
-          }
-          let mut arg = path.pat.to_token_stream().to_string().to_case(Case::Camel);
-          arg.push_str(": ");
-          arg.push_str(&ty_to_ts_type(&path.ty, false));
+  /// The rustdoc `# Arguments` section is converted into JSDoc `@param` tags
+  fn gen_js_doc(&self) -> String {
+    let mut comments: Vec<String> = vec![];
+    let mut in_arguments = false;
+
+    for line in self.comments.iter().flat_map(|c| c.split('\n')) {
+      let trimmed = line.trim();
+
+      if trimmed.starts_with('#') {
+        in_arguments = matches!(
+          trimmed.trim_start_matches('#').trim(),
+          "Arguments" | "Parameters"
+        );
+        if in_arguments {
+          continue;
+        }
+      } else if in_arguments {
+        let arg_doc = trimmed
+          .strip_prefix("* ")
+          .or_else(|| trimmed.strip_prefix("- "))
+          .and_then(parse_arg_doc);
+
+        if let Some((name, desc)) = arg_doc {
+          comments.push(format!(" @param {} {}", name.to_case(Case::Camel), desc));
+          continue;
+        }
 
-          Some(arg)
+        if trimmed.is_empty() {
+          continue;
         }
-        crate::NapiFnArgKind::Callback(cb) => {
-          let mut arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
-          arg.push_str(": ");
-          arg.push_str(&gen_callback_type(cb));
 
-          Some(arg)
+        if let Some(param) = comments.last_mut().filter(|c| c.starts_with(" @param")) {
+          param.push(' ');
+          param.push_str(trimmed);
+          continue;
         }
-      })
+      }
+
+      comments.push(line.to_owned());
+    }
+
+    js_doc_from_comments(&comments)
+  }
+
+  fn gen_ts_func_args(&self) -> String {
+    let required_argc = self.required_arg_count();
+
+    // `this` parameter is erased by TypeScript, it's not counted in the JavaScript arguments
+    let this_arg = self.args.iter().find_map(|arg| match &arg.kind {
+      NapiFnArgKind::PatType(path) if arg.injected() == Some(InjectedArg::This) => {
+        Some(match get_generic_inner_type(&path.ty, &["This"]) {
+          Some(ty) => format!("this: {}", ty_to_ts_type(ty, false)),
+          None => "this: object".to_owned(),
+        })
+      }
+      _ => None,
+    });
+
+    this_arg
+      .into_iter()
+      .chain(
+        self
+          .args
+          .iter()
+          .filter(|arg| arg.injected().is_none())
+          .enumerate()
+          .map(|(i, arg)| match &arg.kind {
+            NapiFnArgKind::PatType(path) => {
+              let name = arg_name(&path.pat);
+              if arg.rest {
+                let elem = get_rest_elem_type(&path.ty).unwrap_or(&path.ty);
+                let elem = ty_to_ts_type(elem, false);
+                if elem.contains(' ') {
+                  format!("...{}: ({})[]", name, elem)
+                } else {
+                  format!("...{}: {}[]", name, elem)
+                }
+              // trailing optional arguments could be omitted
+              } else if i >= required_argc {
+                format!("{}?: {}", name, ty_to_ts_type(&path.ty, false))
+              } else {
+                format!("{}: {}", name, ty_to_ts_type(&path.ty, false))
+              }
+            }
+            NapiFnArgKind::Callback(cb) => {
+              let mut arg = arg_name(&cb.pat);
+              arg.push_str(": ");
+              arg.push_str(&gen_callback_type(cb));
+
+              arg
+            }
+          }),
+      )
       .collect::<Vec<_>>()
       .join(", ")
   }
@@ -76,8 +167,14 @@ impl NapiFn {
         },
         crate::FnKind::Factory => "static",
         crate::FnKind::Constructor => "",
-        crate::FnKind::Getter => "get",
-        crate::FnKind::Setter => "set",
+        crate::FnKind::Getter => match self.fn_self {
+          Some(_) => "get",
+          None => "static get",
+        },
+        crate::FnKind::Setter => match self.fn_self {
+          Some(_) => "set",
+          None => "static set",
+        },
       }
     } else {
       "export function"
@@ -1,8 +1,8 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 
-use super::{ToTypeDef, TypeDef};
-use crate::{ty_to_ts_type, NapiImpl, NapiStruct, NapiStructKind};
+use super::{js_doc_from_comments, ToTypeDef, TypeDef};
+use crate::{codegen::get_option_inner_type, ty_to_ts_type, NapiImpl, NapiStruct, NapiStructKind};
 
 thread_local! {
   pub(crate) static TASK_STRUCTS: RefCell<HashMap<String, String>> = Default::default();
@@ -10,6 +10,16 @@ thread_local! {
 
 impl ToTypeDef for NapiStruct {
   fn to_type_def(&self) -> TypeDef {
+    if self.kind == NapiStructKind::Error {
+      return TypeDef {
+        kind: "error".to_owned(),
+        name: self.js_name.to_owned(),
+        def: format!("export class {} extends Error {{}}", self.js_name),
+        js_doc: js_doc_from_comments(&self.comments),
+        extends: None,
+      };
+    }
+
     TypeDef {
       kind: String::from(if self.kind == NapiStructKind::Object {
         "interface"
@@ -18,6 +28,16 @@ impl ToTypeDef for NapiStruct {
       }),
       name: self.js_name.to_owned(),
       def: self.gen_ts_class(),
+      js_doc: js_doc_from_comments(&self.comments),
+      extends: self.extends.as_ref().map(|parent| {
+        ty_to_ts_type(
+          &syn::Type::Path(syn::TypePath {
+            qself: None,
+            path: parent.clone(),
+          }),
+          false,
+        )
+      }),
     }
   }
 }
@@ -34,11 +54,24 @@ impl ToTypeDef for NapiImpl {
       kind: "impl".to_owned(),
       name: self.js_name.to_owned(),
       def: self
-        .items
+        .consts
         .iter()
-        .map(|f| f.to_type_def().def)
+        .map(|c| {
+          format!(
+            "{}static readonly {}: {}",
+            js_doc_from_comments(&c.comments),
+            c.js_name,
+            ty_to_ts_type(&c.ty, false)
+          )
+        })
+        .chain(self.items.iter().map(|f| {
+          let type_def = f.to_type_def();
+          format!("{}{}", type_def.js_doc, type_def.def)
+        }))
         .collect::<Vec<_>>()
         .join("\\n"),
+      js_doc: String::new(),
+      extends: None,
     }
   }
 }
@@ -51,12 +84,18 @@ impl NapiStruct {
       .iter()
       .filter(|f| f.getter)
       .map(|f| {
-        let mut field_str = String::from("");
+        let mut field_str = js_doc_from_comments(&f.comments);
 
         if !f.setter {
           field_str.push_str("readonly ")
         }
-        let arg = format!("{}: {}", &f.js_name, ty_to_ts_type(&f.ty, false));
+        let arg = match get_option_inner_type(&f.ty) {
+          Some(inner) if self.kind == NapiStructKind::Object => {
+            format!("{}?: {}", &f.js_name, ty_to_ts_type(inner, false))
+          }
+          _ if f.default.is_some() => format!("{}?: {}", &f.js_name, ty_to_ts_type(&f.ty, false)),
+          _ => format!("{}: {}", &f.js_name, ty_to_ts_type(&f.ty, false)),
+        };
         if self.kind == NapiStructKind::Constructor {
           ctor_args.push(arg.clone());
         }
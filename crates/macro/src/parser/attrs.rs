@@ -52,6 +52,12 @@ macro_rules! attrgen {
       (strict, Strict(Span)),
       (object, Object(Span)),
       (task, Task(Span)),
+      (string_enum, StringEnum(Span)),
+      (error, Error(Span)),
+      (default, Default(Span, syn::Expr)),
+      (rest, Rest(Span)),
+      (no_catch_unwind, NoCatchUnwind(Span)),
+      (extends, Extends(Span, syn::Path)),
 
       // impl later
       // (inspectable, Inspectable(Span)),
@@ -162,7 +168,7 @@ macro_rules! methods {
 impl BindgenAttrs {
   /// Find and parse the napi attributes.
   pub fn find(attrs: &mut Vec<syn::Attribute>) -> Result<BindgenAttrs, Diagnostic> {
-    let mut ret = BindgenAttrs::default();
+    let mut ret = <BindgenAttrs as Default>::default();
     loop {
       let napi_attr = attrs
         .iter()
@@ -216,6 +222,7 @@ impl Default for BindgenAttrs {
 macro_rules! gen_bindgen_attr {
   ($( ($method:ident, $($variants:tt)*) ,)*) => {
     /// The possible attributes in the `#[napi]`.
+    #[allow(clippy::large_enum_variant)]
     pub enum BindgenAttr {
       $($($variants)*,)*
     }
@@ -9,8 +9,9 @@ use attrs::{BindgenAttr, BindgenAttrs};
 
 use convert_case::{Case, Casing};
 use napi_derive_backend::{
-  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiEnum, NapiEnumVariant, NapiFn,
-  NapiFnArgKind, NapiImpl, NapiItem, NapiStruct, NapiStructField, NapiStructKind,
+  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiConst, NapiEnum, NapiEnumKind,
+  NapiEnumValue, NapiEnumVariant, NapiEnumVariantField, NapiFn, NapiFnArg, NapiFnArgKind, NapiImpl,
+  NapiItem, NapiStruct, NapiStructField, NapiStructKind,
 };
 use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::ToTokens;
@@ -32,7 +33,7 @@ impl Parse for AnyIdent {
 
 impl Parse for BindgenAttrs {
   fn parse(input: ParseStream) -> SynResult<Self> {
-    let mut attrs = BindgenAttrs::default();
+    let mut attrs = <BindgenAttrs as Default>::default();
     if input.is_empty() {
       return Ok(attrs);
     }
@@ -456,6 +457,35 @@ fn extract_fn_closure_generics(
   Diagnostic::from_vec(errors).and(Ok(map))
 }
 
+fn last_path_segment_is(ty: &syn::Type, name: &str) -> bool {
+  match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => path
+      .segments
+      .last()
+      .is_some_and(|segment| segment.ident == name),
+    _ => false,
+  }
+}
+
+fn is_rest_type(ty: &syn::Type) -> bool {
+  last_path_segment_is(ty, "Rest")
+}
+
+fn is_vec_type(ty: &syn::Type) -> bool {
+  last_path_segment_is(ty, "Vec")
+}
+
+/// `#[napi(...)]` attributes on arguments are only meaningful to the macro
+fn remove_arg_attrs(sig: &mut Signature) -> BindgenResult<()> {
+  for arg in sig.inputs.iter_mut() {
+    if let syn::FnArg::Typed(p) = arg {
+      BindgenAttrs::find(&mut p.attrs)?;
+    }
+  }
+
+  Ok(())
+}
+
 fn napi_fn_from_decl(
   sig: Signature,
   opts: &BindgenAttrs,
@@ -481,14 +511,39 @@ fn napi_fn_from_decl(
     .into_iter()
     .filter_map(|arg| match arg {
       syn::FnArg::Typed(mut p) => {
+        let (default, rest_attr, strict) = match BindgenAttrs::find(&mut p.attrs) {
+          Ok(arg_opts) => (
+            arg_opts.default().cloned(),
+            arg_opts.rest().is_some(),
+            arg_opts.strict().is_some(),
+          ),
+          Err(e) => {
+            errors.push(e);
+            (None, false, false)
+          }
+        };
         let ty_str = p.ty.to_token_stream().to_string();
         if let Some(path_arguments) = callback_traits.get(&ty_str) {
+          if default.is_some() {
+            errors.push(err_span!(
+              p,
+              "callback argument can not have a default value"
+            ));
+          }
+          if rest_attr {
+            errors.push(err_span!(p, "callback argument can not be a rest argument"));
+          }
           match extract_callback_trait_types(path_arguments) {
-            Ok((fn_args, fn_ret)) => Some(NapiFnArgKind::Callback(Box::new(CallbackArg {
-              pat: p.pat,
-              args: fn_args,
-              ret: fn_ret,
-            }))),
+            Ok((fn_args, fn_ret)) => Some(NapiFnArg {
+              kind: NapiFnArgKind::Callback(Box::new(CallbackArg {
+                pat: p.pat,
+                args: fn_args,
+                ret: fn_ret,
+              })),
+              default: None,
+              rest: false,
+              strict: false,
+            }),
             Err(e) => {
               errors.push(e);
               None
@@ -496,8 +551,23 @@ fn napi_fn_from_decl(
           }
         } else {
           let ty = replace_self(*p.ty, parent);
+          let rest = rest_attr || is_rest_type(&ty);
+          if rest_attr && !is_vec_type(&ty) {
+            errors.push(err_span!(ty, "`#[napi(rest)]` argument must be a `Vec<T>`"));
+          }
+          if rest && default.is_some() {
+            errors.push(err_span!(
+              p.pat,
+              "rest argument can not have a default value"
+            ));
+          }
           p.ty = Box::new(ty);
-          Some(NapiFnArgKind::PatType(Box::new(p)))
+          Some(NapiFnArg {
+            kind: NapiFnArgKind::PatType(Box::new(p)),
+            default,
+            rest,
+            strict,
+          })
         }
       }
       syn::FnArg::Receiver(r) => {
@@ -521,6 +591,14 @@ fn napi_fn_from_decl(
     })
     .collect::<Vec<_>>();
 
+  if let Some(pos) = args.iter().position(|arg| arg.rest) {
+    if pos != args.len() - 1 {
+      if let NapiFnArgKind::PatType(p) = &args[pos].kind {
+        errors.push(err_span!(p, "rest argument must be the last argument"));
+      }
+    }
+  }
+
   let (ret, is_ret_result) = match output {
     syn::ReturnType::Default => (None, false),
     syn::ReturnType::Type(_, ty) => {
@@ -572,8 +650,10 @@ fn napi_fn_from_decl(
       kind: fn_kind(opts),
       fn_self,
       parent: parent.cloned(),
+      comments: extract_doc_comments(&attrs),
       attrs,
       strict: opts.strict().is_some(),
+      catch_unwind: opts.no_catch_unwind().is_none(),
     }
   })
 }
@@ -595,8 +675,11 @@ impl ParseNapi for syn::Item {
 
 impl ParseNapi for syn::ItemFn {
   fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    // #[napi] macro will be remove from arguments after converted to ast
+    let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
-    self.convert_to_ast(opts)
+
+    napi
   }
 }
 impl ParseNapi for syn::ItemStruct {
@@ -657,9 +740,10 @@ impl ConvertToAST for syn::ItemFn {
       self.vis.clone(),
       None,
     )?;
+    remove_arg_attrs(&mut self.sig)?;
 
     Ok(Napi {
-      comments: vec![],
+      comments: func.comments.clone(),
       item: NapiItem::Fn(func),
     })
   }
@@ -681,10 +765,54 @@ impl ConvertToAST for syn::ItemStruct {
       NapiStructKind::Constructor
     } else if opts.object().is_some() {
       NapiStructKind::Object
+    } else if opts.error().is_some() {
+      NapiStructKind::Error
     } else {
       NapiStructKind::None
     };
 
+    if struct_kind == NapiStructKind::Error {
+      if opts.extends().is_some() {
+        bail_span!(self.ident, "#[napi(error)] struct can not extend a class");
+      }
+      if !self.fields.is_empty() {
+        bail_span!(self.fields, "#[napi(error)] struct can not have fields");
+      }
+    }
+
+    let extends = opts.extends().cloned();
+    if let Some(parent) = &extends {
+      if struct_kind == NapiStructKind::Object {
+        bail_span!(parent, "#[napi(object)] struct can not extend a class");
+      }
+
+      // the parent class is laid out at the start of the subclass,
+      // so the wrapped subclass instance could be accessed as parent class
+      let parent_ident = extract_path_ident(parent)?;
+      let first_field_is_parent = match self.fields.iter().next().map(|f| get_ty(&f.ty)) {
+        Some(syn::Type::Path(syn::TypePath { qself: None, path })) => {
+          path.segments.last().map(|s| &s.ident) == Some(&parent_ident)
+        }
+        _ => false,
+      };
+      if !first_field_is_parent {
+        bail_span!(
+          self.ident,
+          "struct extending `{}` requires a field of type `{}` as its first field",
+          parent_ident,
+          parent_ident
+        );
+      }
+
+      self.attrs.push(Attribute {
+        pound_token: Default::default(),
+        style: syn::AttrStyle::Outer,
+        bracket_token: Default::default(),
+        path: syn::parse_quote! { repr },
+        tokens: quote! { (C) },
+      });
+    }
+
     for (i, field) in self.fields.iter_mut().enumerate() {
       match field.vis {
         syn::Visibility::Public(..) => {}
@@ -717,6 +845,14 @@ impl ConvertToAST for syn::ItemStruct {
 
       let ignored = field_opts.skip().is_some();
       let readonly = field_opts.readonly().is_some();
+      let default = field_opts.default().cloned();
+
+      if default.is_some() && struct_kind != NapiStructKind::Object {
+        errors.push(err_span!(
+          field,
+          "#[napi(default)] is only supported by fields of #[napi(object)] struct"
+        ));
+      }
 
       fields.push(NapiStructField {
         name,
@@ -724,13 +860,16 @@ impl ConvertToAST for syn::ItemStruct {
         ty: field.ty.clone(),
         getter: !ignored,
         setter: !(ignored || readonly),
+        default,
+        comments: extract_doc_comments(&field.attrs),
       })
     }
 
     record_struct(&struct_name, js_name.clone(), &opts);
+    let comments = extract_doc_comments(&self.attrs);
 
     Diagnostic::from_vec(errors).map(|()| Napi {
-      comments: vec![],
+      comments: comments.clone(),
       item: NapiItem::Struct(NapiStruct {
         js_name,
         name: struct_name,
@@ -738,6 +877,9 @@ impl ConvertToAST for syn::ItemStruct {
         fields,
         is_tuple,
         kind: struct_kind,
+        extends,
+        catch_unwind: opts.no_catch_unwind().is_none(),
+        comments,
       }),
     })
   }
@@ -759,6 +901,7 @@ impl ConvertToAST for syn::ItemImpl {
 
     let mut struct_js_name = struct_name.to_string();
     let mut items = vec![];
+    let mut consts = vec![];
     let mut task_output_type = None;
     for item in self.items.iter_mut() {
       if let Some(method) = match item {
@@ -771,6 +914,25 @@ impl ConvertToAST for syn::ItemImpl {
           }
           None
         }
+        syn::ImplItem::Const(c) => {
+          let opts = BindgenAttrs::find(&mut c.attrs)?;
+
+          if opts.exists {
+            if !matches!(c.vis, Visibility::Public(_)) {
+              bail_span!(c.ident, "only pub const supported by #[napi].");
+            }
+
+            consts.push(NapiConst {
+              name: c.ident.clone(),
+              js_name: opts
+                .js_name()
+                .map_or_else(|| c.ident.to_string(), |(js_name, _)| js_name.to_owned()),
+              ty: c.ty.clone(),
+              comments: extract_doc_comments(&c.attrs),
+            });
+          }
+          None
+        }
         _ => {
           bail_span!(item, "unsupported impl item in #[napi]")
         }
@@ -802,6 +964,7 @@ impl ConvertToAST for syn::ItemImpl {
           vis,
           Some(&struct_name),
         )?;
+        remove_arg_attrs(&mut method.sig)?;
 
         items.push(func);
       }
@@ -813,6 +976,7 @@ impl ConvertToAST for syn::ItemImpl {
         name: struct_name,
         js_name: struct_js_name,
         items,
+        consts,
         task_output_type,
       }),
     })
@@ -829,13 +993,28 @@ impl ConvertToAST for syn::ItemEnum {
       bail_span!(self, "cannot export empty enum to JS");
     }
 
-    self.attrs.push(Attribute {
-      pound_token: Default::default(),
-      style: syn::AttrStyle::Outer,
-      bracket_token: Default::default(),
-      path: syn::parse_quote! { derive },
-      tokens: quote! { (Copy, Clone) },
-    });
+    let kind = if opts.string_enum().is_some() {
+      NapiEnumKind::String
+    } else if self
+      .variants
+      .iter()
+      .any(|v| !matches!(v.fields, syn::Fields::Unit))
+    {
+      NapiEnumKind::Object
+    } else {
+      NapiEnumKind::Number
+    };
+
+    // data-carrying enums may own non-Copy fields
+    if kind != NapiEnumKind::Object {
+      self.attrs.push(Attribute {
+        pound_token: Default::default(),
+        style: syn::AttrStyle::Outer,
+        bracket_token: Default::default(),
+        path: syn::parse_quote! { derive },
+        tokens: quote! { (Copy, Clone) },
+      });
+    }
 
     let js_name = opts
       .js_name()
@@ -844,12 +1023,65 @@ impl ConvertToAST for syn::ItemEnum {
     let mut last_variant_val: i32 = -1;
     let variants = self
       .variants
-      .iter()
+      .iter_mut()
       .map(|v| {
-        match v.fields {
-          syn::Fields::Unit => {}
-          _ => bail_span!(v.fields, "Structured enum is not supported in #[napi]"),
-        };
+        let variant_opts = BindgenAttrs::find(&mut v.attrs)?;
+        let comments = extract_doc_comments(&v.attrs);
+
+        if kind != NapiEnumKind::Number {
+          if let Some((_, expr)) = &v.discriminant {
+            bail_span!(
+              expr,
+              "explicit discriminant is only supported by number enum in #[napi]"
+            );
+          }
+
+          let val = variant_opts
+            .js_name()
+            .map_or_else(|| v.ident.to_string(), |(s, _)| s.to_owned());
+
+          if kind == NapiEnumKind::String {
+            match v.fields {
+              syn::Fields::Unit => {}
+              _ => bail_span!(
+                v.fields,
+                "variants of #[napi(string_enum)] can not carry data"
+              ),
+            };
+          }
+
+          let fields = v
+            .fields
+            .iter_mut()
+            .enumerate()
+            .map(|(i, field)| {
+              let field_opts = BindgenAttrs::find(&mut field.attrs)?;
+              let (js_name, name) = match &field.ident {
+                Some(ident) => (
+                  field_opts.js_name().map_or_else(
+                    || ident.to_string().to_case(Case::Camel),
+                    |(js_name, _)| js_name.to_owned(),
+                  ),
+                  syn::Member::Named(ident.clone()),
+                ),
+                None => (format!("field{}", i), syn::Member::Unnamed(i.into())),
+              };
+
+              Ok(NapiEnumVariantField {
+                name,
+                js_name,
+                ty: field.ty.clone(),
+              })
+            })
+            .collect::<BindgenResult<Vec<_>>>()?;
+
+          return Ok(NapiEnumVariant {
+            name: v.ident.clone(),
+            val: NapiEnumValue::String(val),
+            fields,
+            comments,
+          });
+        }
 
         let val = match &v.discriminant {
           Some((_, expr)) => {
@@ -890,10 +1122,10 @@ impl ConvertToAST for syn::ItemEnum {
         };
 
         last_variant_val = val;
-        let comments = extract_doc_comments(&v.attrs);
         Ok(NapiEnumVariant {
           name: v.ident.clone(),
-          val,
+          val: NapiEnumValue::Number(val),
+          fields: vec![],
           comments,
         })
       })
@@ -902,11 +1134,13 @@ impl ConvertToAST for syn::ItemEnum {
     let comments = extract_doc_comments(&self.attrs);
 
     Ok(Napi {
-      comments,
+      comments: comments.clone(),
       item: NapiItem::Enum(NapiEnum {
         name: self.ident.clone(),
         js_name,
         variants,
+        kind,
+        comments,
       }),
     })
   }
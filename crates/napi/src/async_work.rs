@@ -1,12 +1,15 @@
+use std::ffi::CString;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
 use std::sync::atomic::{AtomicU8, Ordering};
-use std::{ffi::CString, rc::Rc};
+use std::sync::Arc;
 
 use crate::{
-  bindgen_runtime::ToNapiValue, check_status, js_values::NapiValue, sys, Env, JsError, JsObject,
-  Result, Task,
+  bindgen_runtime::{catch_panic, ToNapiValue},
+  check_status,
+  js_values::NapiValue,
+  sys, Env, JsError, JsObject, Result, Task,
 };
 
 struct AsyncWork<T: Task> {
@@ -14,7 +17,7 @@ struct AsyncWork<T: Task> {
   deferred: sys::napi_deferred,
   value: Result<mem::MaybeUninit<T::Output>>,
   napi_async_work: sys::napi_async_work,
-  status: Rc<AtomicU8>,
+  status: Arc<AtomicU8>,
 }
 
 pub struct AsyncWorkPromise {
@@ -26,7 +29,7 @@ pub struct AsyncWorkPromise {
   /// 0: not started
   /// 1: completed
   /// 2: canceled
-  pub(crate) status: Rc<AtomicU8>,
+  pub(crate) status: Arc<AtomicU8>,
 }
 
 impl AsyncWorkPromise {
@@ -44,14 +47,14 @@ impl AsyncWorkPromise {
 pub fn run<T: Task>(
   env: sys::napi_env,
   task: T,
-  abort_status: Option<Rc<AtomicU8>>,
+  abort_status: Option<Arc<AtomicU8>>,
 ) -> Result<AsyncWorkPromise> {
   let mut raw_resource = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_object(env, &mut raw_resource) })?;
   let mut raw_promise = ptr::null_mut();
   let mut deferred = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) })?;
-  let task_status = abort_status.unwrap_or_else(|| Rc::new(AtomicU8::new(0)));
+  let task_status = abort_status.unwrap_or_else(|| Arc::new(AtomicU8::new(0)));
   let result = Box::leak(Box::new(AsyncWork {
     inner_task: task,
     deferred,
@@ -91,10 +94,8 @@ unsafe impl<T: Task> Sync for AsyncWork<T> {}
 /// So it actually could do nothing here, because `execute` function is called in the other thread mostly.
 unsafe extern "C" fn execute<T: Task>(_env: sys::napi_env, data: *mut c_void) {
   let mut work = Box::from_raw(data as *mut AsyncWork<T>);
-  let _ = mem::replace(
-    &mut work.value,
-    work.inner_task.compute().map(mem::MaybeUninit::new),
-  );
+  let value = catch_panic(|| work.inner_task.compute());
+  let _ = mem::replace(&mut work.value, value.map(mem::MaybeUninit::new));
   Box::leak(work);
 }
 
@@ -107,13 +108,13 @@ unsafe extern "C" fn complete<T: Task>(
   let value_ptr = mem::replace(&mut work.value, Ok(mem::MaybeUninit::zeroed()));
   let deferred = mem::replace(&mut work.deferred, ptr::null_mut());
   let napi_async_work = mem::replace(&mut work.napi_async_work, ptr::null_mut());
-  let value = match value_ptr {
+  let value = catch_panic(|| match value_ptr {
     Ok(v) => {
       let output = v.assume_init();
       work.inner_task.resolve(Env::from_raw(env), output)
     }
     Err(e) => work.inner_task.reject(Env::from_raw(env), e),
-  };
+  });
   if status != sys::Status::napi_cancelled && work.status.load(Ordering::Relaxed) != 2 {
     match check_status!(status)
       .and_then(move |_| value)
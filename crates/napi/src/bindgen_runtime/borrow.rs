@@ -0,0 +1,269 @@
+use std::cell::{Cell, RefCell};
+use std::ffi::c_void;
+use std::ptr;
+
+use super::with_env_data;
+use crate::{bindgen_prelude::*, check_status, sys, Result};
+
+const EXCLUSIVE: isize = -1;
+
+/// The native object wrapped into a JavaScript class instance.
+///
+/// The borrow state is tracked like a `RefCell`, the borrows of class instances
+/// are released when the napi callback which takes them returns.
+struct WrappedObject {
+  /// Count of shared borrows, or `EXCLUSIVE` for an exclusive borrow
+  borrow: Cell<isize>,
+  value: *mut c_void,
+}
+
+thread_local! {
+  /// Borrows taken in the active napi callbacks
+  static ACTIVE_BORROWS: RefCell<Vec<*const WrappedObject>> = Default::default();
+  static BORROW_SCOPE_DEPTH: Cell<usize> = Default::default();
+}
+
+/// Releases the borrows of class instances taken since it's created, on drop.
+///
+/// Created for every napi callback generated by `#[napi]`.
+pub struct BorrowScope {
+  start: usize,
+}
+
+impl BorrowScope {
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    BORROW_SCOPE_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    BorrowScope {
+      start: ACTIVE_BORROWS.with(|borrows| borrows.borrow().len()),
+    }
+  }
+}
+
+impl Drop for BorrowScope {
+  fn drop(&mut self) {
+    let released = ACTIVE_BORROWS.with(|borrows| borrows.borrow_mut().split_off(self.start));
+    for obj in released.into_iter().rev() {
+      let borrow = unsafe { &(*obj).borrow };
+      if borrow.get() == EXCLUSIVE {
+        borrow.set(0);
+      } else {
+        borrow.set(borrow.get() - 1);
+      }
+    }
+    BORROW_SCOPE_DEPTH.with(|depth| depth.set(depth.get() - 1));
+  }
+}
+
+/// Borrows taken out of any `BorrowScope` are checked but not recorded,
+/// since there is no point to release them.
+fn record_borrow(obj: &WrappedObject, state: isize) {
+  if BORROW_SCOPE_DEPTH.with(|depth| depth.get()) > 0 {
+    obj.borrow.set(state);
+    ACTIVE_BORROWS.with(|borrows| borrows.borrow_mut().push(obj));
+  }
+}
+
+/// Type tag of the class generated by struct `rust_name`.
+///
+/// The address of a static is unique to the loaded addon,
+/// so the classes of different addons are tagged differently.
+#[cfg(feature = "napi8")]
+fn class_type_tag(rust_name: &str) -> sys::napi_type_tag {
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::{Hash, Hasher};
+
+  static ADDON_TAG: u8 = 0;
+
+  let mut hasher = DefaultHasher::new();
+  rust_name.hash(&mut hasher);
+
+  sys::napi_type_tag {
+    lower: hasher.finish(),
+    upper: &ADDON_TAG as *const u8 as u64,
+  }
+}
+
+/// Check `js_object` is an instance of the class generated by `T`, or its subclasses.
+///
+/// The `TypeError` is thrown into JavaScript if it's not.
+unsafe fn check_class_instance<T: TypeName>(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+) -> Result<()> {
+  let rust_name = T::type_name();
+  let (js_name, is_instance) = with_env_data(env, |env_data| -> Result<_> {
+    let class = match env_data.classes.get(rust_name) {
+      Some(class) => class,
+      None => return Ok((rust_name, false)),
+    };
+
+    #[cfg(feature = "napi8")]
+    for compatible_class in class.compatible_classes.iter() {
+      let mut is_instance = false;
+      let type_tag = class_type_tag(compatible_class);
+      check_status!(
+        sys::napi_check_object_type_tag(env, js_object, &type_tag, &mut is_instance),
+        "Failed to check type tag of `{}`",
+        class.js_name,
+      )?;
+
+      if is_instance {
+        return Ok((class.js_name, true));
+      }
+    }
+
+    #[cfg(not(feature = "napi8"))]
+    {
+      // the prototype chain could be changed in JavaScript, it's the best we can do without type tags
+      let mut constructor = ptr::null_mut();
+      check_status!(
+        sys::napi_get_reference_value(env, class.constructor, &mut constructor),
+        "Failed to get constructor of class `{}`",
+        class.js_name,
+      )?;
+      let mut is_instance = false;
+      check_status!(
+        sys::napi_instanceof(env, js_object, constructor, &mut is_instance),
+        "Failed to check instance of class `{}`",
+        class.js_name,
+      )?;
+
+      if is_instance {
+        return Ok((class.js_name, true));
+      }
+    }
+
+    Ok((class.js_name, false))
+  })??;
+
+  if is_instance {
+    return Ok(());
+  }
+
+  Err(
+    Error::new(
+      Status::InvalidArg,
+      format!("Expect value to be an instance of class `{}`", js_name),
+    )
+    .with_kind(ErrorKind::TypeError),
+  )
+}
+
+/// Wrap `value` into the JavaScript object created by the class constructor
+pub(crate) unsafe fn wrap_object<T: TypeName>(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+  value: T,
+  js_name: &str,
+) -> Result<()> {
+  let obj = Box::new(WrappedObject {
+    borrow: Cell::new(0),
+    value: Box::into_raw(Box::new(value)) as *mut c_void,
+  });
+
+  check_status!(
+    sys::napi_wrap(
+      env,
+      js_object,
+      Box::into_raw(obj) as *mut c_void,
+      Some(finalize_wrapped_object::<T>),
+      ptr::null_mut(),
+      ptr::null_mut(),
+    ),
+    "Failed to initialize class `{}`",
+    js_name,
+  )?;
+
+  #[cfg(feature = "napi8")]
+  check_status!(
+    sys::napi_type_tag_object(env, js_object, &class_type_tag(T::type_name())),
+    "Failed to tag class `{}`",
+    js_name,
+  )?;
+
+  Ok(())
+}
+
+unsafe extern "C" fn finalize_wrapped_object<T>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let obj = Box::from_raw(finalize_data as *mut WrappedObject);
+  // panics can't be thrown in finalizers, they are only reported by the panic hook
+  let _ = catch_panic(|| {
+    drop(Box::from_raw(obj.value as *mut T));
+    Ok(())
+  });
+}
+
+unsafe fn unwrap_object<T: TypeName>(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+) -> Result<&'static WrappedObject> {
+  check_class_instance::<T>(env, js_object)?;
+
+  let mut wrapped_val: *mut c_void = ptr::null_mut();
+
+  check_status!(
+    sys::napi_unwrap(env, js_object, &mut wrapped_val),
+    "Failed to recover `{}` type from napi value",
+    T::type_name(),
+  )?;
+
+  Ok(&*(wrapped_val as *const WrappedObject))
+}
+
+/// Shared reference of the native object wrapped in `js_object`,
+/// fails if the object is exclusively borrowed.
+///
+/// # Safety
+///
+/// `js_object` must be an instance of the class generated from `T`, or its subclasses
+pub unsafe fn borrow_wrapped<T: TypeName>(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+) -> Result<&'static T> {
+  let obj = unwrap_object::<T>(env, js_object)?;
+  let borrow = obj.borrow.get();
+
+  if borrow == EXCLUSIVE {
+    return Err(Error::new(
+      Status::GenericFailure,
+      format!(
+        "Failed to borrow `{}`, it's already mutably borrowed",
+        T::type_name()
+      ),
+    ));
+  }
+  record_borrow(obj, borrow + 1);
+
+  Ok(&*(obj.value as *const T))
+}
+
+/// Exclusive reference of the native object wrapped in `js_object`,
+/// fails if the object is already borrowed.
+///
+/// # Safety
+///
+/// `js_object` must be an instance of the class generated from `T`, or its subclasses
+pub unsafe fn borrow_wrapped_mut<T: TypeName>(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+) -> Result<&'static mut T> {
+  let obj = unwrap_object::<T>(env, js_object)?;
+
+  if obj.borrow.get() != 0 {
+    return Err(Error::new(
+      Status::GenericFailure,
+      format!(
+        "Failed to borrow `{}` mutably, it's already borrowed",
+        T::type_name()
+      ),
+    ));
+  }
+  record_borrow(obj, EXCLUSIVE);
+
+  Ok(&mut *(obj.value as *mut T))
+}
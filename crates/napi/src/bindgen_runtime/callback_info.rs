@@ -1,8 +1,7 @@
-use std::ffi::c_void;
 use std::ptr;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::{bindgen_prelude::*, check_status, sys, Result};
+use crate::{bindgen_prelude::*, check_status, sys, type_of, Result, ValueType};
 
 #[doc(hidden)]
 /// Determined is `constructor` called from Class `factory`
@@ -12,7 +11,9 @@ pub static ___CALL_FROM_FACTORY: AtomicBool = AtomicBool::new(false);
 
 pub struct CallbackInfo<const N: usize> {
   env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
   this: sys::napi_value,
+  argc: usize,
   pub args: [sys::napi_value; N],
 }
 
@@ -42,77 +43,111 @@ impl<const N: usize> CallbackInfo<N> {
 
     if let Some(required_argc) = required_argc {
       if required_argc > argc {
-        return Err(Error::new(
-          Status::InvalidArg,
-          format!(
-            "{} arguments required by received {}.",
-            required_argc, &argc
-          ),
-        ));
+        return Err(
+          Error::new(
+            Status::InvalidArg,
+            format!(
+              "Expected at least {} arguments, got {}",
+              required_argc, argc
+            ),
+          )
+          .with_kind(ErrorKind::TypeError),
+        );
       }
     }
 
-    Ok(Self { env, this, args })
+    Ok(Self {
+      env,
+      callback_info,
+      this,
+      argc,
+      args,
+    })
   }
 
   pub fn get_arg(&self, index: usize) -> sys::napi_value {
     self.args[index]
   }
 
-  pub fn this(&self) -> sys::napi_value {
-    self.this
+  pub fn new_target(&self) -> Result<NewTarget> {
+    let mut new_target = ptr::null_mut();
+    unsafe {
+      check_status!(
+        sys::napi_get_new_target(self.env, self.callback_info, &mut new_target),
+        "Failed to get new.target"
+      )?;
+    }
+
+    Ok(NewTarget::new(self.env, new_target))
   }
 
-  pub fn construct<T>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
-    let obj = Box::new(obj);
-    let this = self.this();
+  /// Convert all the arguments from `start` into `T`
+  pub fn rest<T: FromNapiValue>(&self, start: usize) -> Result<Vec<T>> {
+    if start >= self.argc {
+      return Ok(vec![]);
+    }
 
+    // `argc` is the count of all the arguments passed, not limited by `N`
+    let mut argc = self.argc;
+    let mut args = vec![ptr::null_mut(); argc];
     unsafe {
       check_status!(
-        sys::napi_wrap(
+        sys::napi_get_cb_info(
           self.env,
-          this,
-          Box::into_raw(obj) as *mut std::ffi::c_void,
-          Some(raw_finalize_unchecked::<T>),
+          self.callback_info,
+          &mut argc,
+          args.as_mut_ptr(),
+          ptr::null_mut(),
           ptr::null_mut(),
-          &mut std::ptr::null_mut()
         ),
-        "Failed to initialize class `{}`",
-        js_name,
+        "Failed to get rest arguments"
       )?;
-    };
+    }
+
+    args[start..]
+      .iter()
+      .map(|arg| unsafe { T::from_napi_value(self.env, *arg) })
+      .collect()
+  }
+
+  /// Whether the argument at `index` is `undefined` or not passed
+  pub fn is_undefined(&self, index: usize) -> Result<bool> {
+    if index >= self.argc {
+      return Ok(true);
+    }
+
+    let value_type = unsafe { type_of!(self.env, self.args[index])? };
+    Ok(value_type == ValueType::Undefined)
+  }
+
+  pub fn this(&self) -> sys::napi_value {
+    self.this
+  }
+
+  pub fn construct<T: TypeName>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
+    let this = self.this();
+
+    unsafe { wrap_object(self.env, this, obj, js_name)? };
 
     Ok(this)
   }
 
-  pub fn factory<T>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
-    let obj = Box::new(obj);
+  pub fn factory<T: TypeName>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
     let this = self.this();
     let mut instance = ptr::null_mut();
     unsafe {
       ___CALL_FROM_FACTORY.store(true, Ordering::Relaxed);
       let status = sys::napi_new_instance(self.env, this, 0, ptr::null_mut(), &mut instance);
       ___CALL_FROM_FACTORY.store(false, Ordering::Relaxed);
-      // Error thrown in `constructor`
-      if status == sys::Status::napi_pending_exception {
-        let mut exception = ptr::null_mut();
-        sys::napi_get_and_clear_last_exception(self.env, &mut exception);
-        sys::napi_throw(self.env, exception);
-        return Ok(ptr::null_mut());
-      }
-
-      check_status!(
-        sys::napi_wrap(
-          self.env,
-          instance,
-          Box::into_raw(obj) as *mut std::ffi::c_void,
-          Some(raw_finalize_unchecked::<T>),
-          ptr::null_mut(),
-          &mut std::ptr::null_mut()
-        ),
-        "Failed to initialize class `{}`",
-        js_name,
+      // Error thrown in `constructor` is rethrown unchanged
+      check_pending_exception!(
+        self.env,
+        status,
+        "Failed to create instance of `{}`",
+        js_name
       )?;
+
+      wrap_object(self.env, instance, obj, js_name)?;
     };
 
     Ok(instance)
@@ -122,33 +157,13 @@ impl<const N: usize> CallbackInfo<N> {
   where
     T: FromNapiMutRef + TypeName,
   {
-    let mut wrapped_val: *mut c_void = std::ptr::null_mut();
-
-    unsafe {
-      check_status!(
-        sys::napi_unwrap(self.env, self.this, &mut wrapped_val),
-        "Failed to unwrap exclusive reference of `{}` type from napi value",
-        T::type_name(),
-      )?;
-
-      Ok(&mut *(wrapped_val as *mut T))
-    }
+    unsafe { borrow_wrapped_mut::<T>(self.env, self.this) }
   }
 
   pub fn unwrap_borrow<T>(&mut self) -> Result<&'static T>
   where
     T: FromNapiRef + TypeName,
   {
-    let mut wrapped_val: *mut c_void = std::ptr::null_mut();
-
-    unsafe {
-      check_status!(
-        sys::napi_unwrap(self.env, self.this, &mut wrapped_val),
-        "Failed to unwrap shared reference of `{}` type from napi value",
-        T::type_name(),
-      )?;
-
-      Ok(&*(wrapped_val as *const T))
-    }
+    unsafe { borrow_wrapped::<T>(self.env, self.this) }
   }
 }
@@ -1,3 +1,9 @@
+use std::ffi::CString;
+use std::ptr;
+
+use super::{set_class_parent, with_env_data};
+use crate::{bindgen_prelude::*, check_pending_exception, check_status, sys, Result};
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! check_status_or_throw {
@@ -8,3 +14,238 @@ macro_rules! check_status_or_throw {
     }
   };
 }
+
+/// Create the class `js_name` extending `Error`, generated by `#[napi(error)]`.
+///
+/// The constructor is stored in the env data, so `ErrorKind::Custom(js_name)` errors
+/// are created as instances of it.
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread
+pub unsafe fn create_error_class(
+  env: sys::napi_env,
+  js_name: &'static str,
+) -> Result<sys::napi_value> {
+  let class_name = CString::new(js_name)?;
+  let mut class = ptr::null_mut();
+  check_status!(
+    sys::napi_define_class(
+      env,
+      class_name.as_ptr(),
+      js_name.len(),
+      Some(construct_error),
+      ptr::null_mut(),
+      0,
+      ptr::null(),
+      &mut class,
+    ),
+    "Failed to create error class `{}`",
+    js_name,
+  )?;
+
+  let error_class = get_named_property(env, get_global(env)?, "Error")?;
+  set_class_parent(env, class, error_class)?;
+
+  let name = String::to_napi_value(env, js_name.to_owned())?;
+  let name_property = sys::napi_property_descriptor {
+    utf8name: "name\0".as_ptr() as *const _,
+    name: ptr::null_mut(),
+    method: None,
+    getter: None,
+    setter: None,
+    value: name,
+    attributes: sys::napi_property_attributes::napi_writable,
+    data: ptr::null_mut(),
+  };
+  check_status!(
+    sys::napi_define_properties(
+      env,
+      get_named_property(env, class, "prototype")?,
+      1,
+      &name_property,
+    ),
+    "Failed to set name of error class `{}`",
+    js_name,
+  )?;
+
+  let mut constructor = ptr::null_mut();
+  check_status!(
+    sys::napi_create_reference(env, class, 1, &mut constructor),
+    "Failed to create reference of error class `{}`",
+    js_name,
+  )?;
+  let replaced = with_env_data(env, |env_data| {
+    env_data.error_classes.insert(js_name, constructor)
+  })?;
+  if let Some(replaced) = replaced {
+    sys::napi_delete_reference(env, replaced);
+  }
+
+  Ok(class)
+}
+
+/// The instances are created by `Reflect.construct(Error, args, new.target)`,
+/// a real `Error` is required to get the stack traces.
+unsafe extern "C" fn construct_error(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  match catch_panic(|| construct_error_instance(env, callback_info)) {
+    Ok(js_error) => js_error,
+    Err(e) => {
+      JsError::from(e).throw_into(env);
+      ptr::null_mut()
+    }
+  }
+}
+
+unsafe fn construct_error_instance(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> Result<sys::napi_value> {
+  // `message` and `options`
+  let mut argc = 2;
+  let mut argv = [ptr::null_mut(); 2];
+  check_status!(
+    sys::napi_get_cb_info(
+      env,
+      callback_info,
+      &mut argc,
+      argv.as_mut_ptr(),
+      ptr::null_mut(),
+      ptr::null_mut(),
+    ),
+    "Failed to get arguments of error class"
+  )?;
+
+  let mut new_target = ptr::null_mut();
+  check_status!(
+    sys::napi_get_new_target(env, callback_info, &mut new_target),
+    "Failed to get new.target"
+  )?;
+  if new_target.is_null() {
+    return Err(
+      Error::new(
+        Status::InvalidArg,
+        "Class constructor of error cannot be invoked without 'new'".to_owned(),
+      )
+      .with_kind(ErrorKind::TypeError),
+    );
+  }
+
+  let argc = argc.min(argv.len());
+  let mut args = ptr::null_mut();
+  check_status!(
+    sys::napi_create_array_with_length(env, argc, &mut args),
+    "Failed to create arguments of error class"
+  )?;
+  for (i, arg) in argv[..argc].iter().enumerate() {
+    check_status!(
+      sys::napi_set_element(env, args, i as u32, *arg),
+      "Failed to create arguments of error class"
+    )?;
+  }
+
+  let global = get_global(env)?;
+  let error_class = get_named_property(env, global, "Error")?;
+  let reflect = get_named_property(env, global, "Reflect")?;
+  let construct = get_named_property(env, reflect, "construct")?;
+
+  let mut js_error = ptr::null_mut();
+  check_pending_exception!(
+    env,
+    sys::napi_call_function(
+      env,
+      reflect,
+      construct,
+      3,
+      [error_class, args, new_target].as_ptr(),
+      &mut js_error,
+    ),
+    "Failed to construct error"
+  )?;
+
+  Ok(js_error)
+}
+
+/// The stack of the error created in native code is empty,
+/// `Error` skips the frames until `new.target` which is not seen by V8 in that case.
+unsafe fn capture_stack_trace(env: sys::napi_env, js_error: sys::napi_value) -> Result<()> {
+  let error_class = get_named_property(env, get_global(env)?, "Error")?;
+  let capture = get_named_property(env, error_class, "captureStackTrace")?;
+  let mut capture_type = 0;
+  check_status!(
+    sys::napi_typeof(env, capture, &mut capture_type),
+    "Failed to get type of `Error.captureStackTrace`"
+  )?;
+  // it's not standard, only supported by V8
+  if capture_type != sys::ValueType::napi_function {
+    return Ok(());
+  }
+
+  check_pending_exception!(
+    env,
+    sys::napi_call_function(env, error_class, capture, 1, &js_error, ptr::null_mut()),
+    "Failed to capture stack trace of error"
+  )
+}
+
+unsafe fn get_global(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    sys::napi_get_global(env, &mut global),
+    "Failed to get global object"
+  )?;
+
+  Ok(global)
+}
+
+unsafe fn get_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+) -> Result<sys::napi_value> {
+  let c_name = CString::new(name)?;
+  let mut value = ptr::null_mut();
+  check_status!(
+    sys::napi_get_named_property(env, object, c_name.as_ptr(), &mut value),
+    "Failed to get `{}`",
+    name,
+  )?;
+
+  Ok(value)
+}
+
+/// `new JsName(message)`, falls back to `Error` if the class is not created in `env`
+pub(crate) unsafe fn new_error_class_instance(
+  env: sys::napi_env,
+  js_name: &'static str,
+  message: sys::napi_value,
+) -> Result<sys::napi_value> {
+  let constructor = with_env_data(env, |env_data| env_data.error_classes.get(js_name).copied())?;
+  let mut js_error = ptr::null_mut();
+
+  match constructor {
+    Some(constructor) => {
+      let mut class = ptr::null_mut();
+      check_status!(
+        sys::napi_get_reference_value(env, constructor, &mut class),
+        "Failed to get error class `{}`",
+        js_name,
+      )?;
+      check_status!(
+        sys::napi_new_instance(env, class, 1, &message, &mut js_error),
+        "Failed to create `{}`",
+        js_name,
+      )?;
+      capture_stack_trace(env, js_error)?;
+    }
+    None => check_status!(
+      sys::napi_create_error(env, ptr::null_mut(), message, &mut js_error),
+      "Failed to create Error"
+    )?,
+  }
+
+  Ok(js_error)
+}
@@ -2,25 +2,47 @@ use crate::{check_status, sys, Error, JsUnknown, NapiRaw, NapiValue, Result, Sta
 use std::ptr;
 
 mod array;
+mod arraybuffer;
+#[cfg(feature = "napi6")]
+mod bigint;
 mod boolean;
 mod buffer;
+#[cfg(feature = "napi5")]
+mod date;
 mod either;
+mod external;
+mod function;
 mod map;
 mod nil;
 mod number;
 mod object;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+mod promise;
+mod rest;
 #[cfg(feature = "serde-json")]
 mod serde;
 mod string;
 mod task;
+mod this;
 
 pub use array::*;
+pub use arraybuffer::*;
+#[cfg(feature = "napi6")]
+pub use bigint::*;
 pub use buffer::*;
+#[cfg(feature = "napi5")]
+pub use date::*;
 pub use either::*;
+pub use external::*;
+pub use function::*;
 pub use nil::*;
 pub use object::*;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub use promise::*;
+pub use rest::*;
 pub use string::*;
 pub use task::*;
+pub use this::*;
 
 #[cfg(feature = "latin1")]
 pub use string::latin1_string::*;
@@ -181,17 +203,7 @@ where
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     match val {
       Ok(v) => T::to_napi_value(env, v),
-      Err(e) => {
-        let error_code = String::to_napi_value(env, format!("{:?}", e.status))?;
-        let reason = String::to_napi_value(env, e.reason)?;
-        let mut error = ptr::null_mut();
-        check_status!(
-          sys::napi_create_error(env, error_code, reason, &mut error),
-          "Failed to create napi error"
-        )?;
-
-        Ok(error)
-      }
+      Err(e) => e.into_js_error(env),
     }
   }
 }
@@ -0,0 +1,369 @@
+use std::ffi::c_void;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+
+use crate::{bindgen_prelude::*, check_status, sys, TypedArrayType, ValueType};
+
+/// Backing storage of the typed arrays in bindgen runtime
+enum TypedArrayData<T> {
+  /// Data allocated by Rust, will be moved into an external `ArrayBuffer` when converted into napi value
+  Owned(Vec<T>),
+  /// Data borrowed from a JavaScript `TypedArray`, the `napi_ref` keeps it alive until dropped
+  Borrowed {
+    env: sys::napi_env,
+    reference: sys::napi_ref,
+    data: *mut T,
+    length: usize,
+  },
+}
+
+impl<T> TypedArrayData<T> {
+  fn as_slice(&self) -> &[T] {
+    match self {
+      Self::Owned(vec) => vec.as_slice(),
+      Self::Borrowed { data, length, .. } => {
+        if data.is_null() || *length == 0 {
+          &[]
+        } else {
+          unsafe { slice::from_raw_parts(*data, *length) }
+        }
+      }
+    }
+  }
+
+  fn as_mut_slice(&mut self) -> &mut [T] {
+    match self {
+      Self::Owned(vec) => vec.as_mut_slice(),
+      Self::Borrowed { data, length, .. } => {
+        if data.is_null() || *length == 0 {
+          &mut []
+        } else {
+          unsafe { slice::from_raw_parts_mut(*data, *length) }
+        }
+      }
+    }
+  }
+}
+
+impl<T> Drop for TypedArrayData<T> {
+  fn drop(&mut self) {
+    if let Self::Borrowed { env, reference, .. } = self {
+      let status = unsafe { sys::napi_delete_reference(*env, *reference) };
+      debug_assert!(
+        status == sys::Status::napi_ok,
+        "Delete TypedArray reference failed"
+      );
+    }
+  }
+}
+
+/// # Safety
+///
+/// called when the external `ArrayBuffer` created from a Rust `Vec<T>` is ready for gc
+unsafe extern "C" fn drop_typed_array_data<T>(
+  _env: sys::napi_env,
+  _finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  mem::drop(Box::from_raw(finalize_hint as *mut Vec<T>));
+}
+
+unsafe fn get_typed_array_info(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  expected_types: &[TypedArrayType],
+  type_name: &str,
+) -> Result<(*mut c_void, usize)> {
+  let mut is_typed_array = false;
+  check_status!(
+    sys::napi_is_typedarray(env, napi_val, &mut is_typed_array),
+    "Failed to check given napi value is `{}`",
+    type_name,
+  )?;
+
+  if !is_typed_array {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Expect value to be {}, but received non-TypedArray",
+        type_name
+      ),
+    ));
+  }
+
+  let mut typed_array_type = 0;
+  let mut length = 0;
+  let mut data = ptr::null_mut();
+  let mut arraybuffer = ptr::null_mut();
+  let mut byte_offset = 0;
+
+  check_status!(
+    sys::napi_get_typedarray_info(
+      env,
+      napi_val,
+      &mut typed_array_type,
+      &mut length,
+      &mut data,
+      &mut arraybuffer,
+      &mut byte_offset,
+    ),
+    "Failed to get `{}` info",
+    type_name,
+  )?;
+
+  let received_type = TypedArrayType::from(typed_array_type);
+  if !expected_types.contains(&received_type) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Expect value to be {}, but received {:?}Array",
+        type_name, received_type
+      ),
+    ));
+  }
+
+  Ok((data, length))
+}
+
+unsafe fn typed_array_from_napi_value<T>(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  expected_type: TypedArrayType,
+  type_name: &str,
+) -> Result<TypedArrayData<T>> {
+  let (data, length) = get_typed_array_info(env, napi_val, &[expected_type], type_name)?;
+
+  let mut reference = ptr::null_mut();
+  check_status!(
+    sys::napi_create_reference(env, napi_val, 1, &mut reference),
+    "Failed to create reference of `{}`",
+    type_name,
+  )?;
+
+  Ok(TypedArrayData::Borrowed {
+    env,
+    reference,
+    data: data as *mut T,
+    length,
+  })
+}
+
+unsafe fn typed_array_to_napi_value<T>(
+  env: sys::napi_env,
+  mut val: TypedArrayData<T>,
+  typed_array_type: TypedArrayType,
+  type_name: &str,
+) -> Result<sys::napi_value> {
+  match &mut val {
+    TypedArrayData::Owned(vec) => {
+      let mut vec = mem::take(vec);
+      let length = vec.len();
+      let data = vec.as_mut_ptr();
+      let mut arraybuffer = ptr::null_mut();
+
+      check_status!(
+        sys::napi_create_external_arraybuffer(
+          env,
+          data as *mut c_void,
+          length * mem::size_of::<T>(),
+          Some(drop_typed_array_data::<T>),
+          Box::into_raw(Box::new(vec)) as *mut c_void,
+          &mut arraybuffer,
+        ),
+        "Failed to create ArrayBuffer of `{}`",
+        type_name,
+      )?;
+
+      let mut ret = ptr::null_mut();
+      check_status!(
+        sys::napi_create_typedarray(
+          env,
+          typed_array_type.into(),
+          length,
+          arraybuffer,
+          0,
+          &mut ret
+        ),
+        "Failed to create `{}`",
+        type_name,
+      )?;
+
+      Ok(ret)
+    }
+    TypedArrayData::Borrowed { reference, .. } => {
+      let mut ret = ptr::null_mut();
+      check_status!(
+        sys::napi_get_reference_value(env, *reference, &mut ret),
+        "Failed to get `{}` from reference",
+        type_name,
+      )?;
+
+      Ok(ret)
+    }
+  }
+}
+
+macro_rules! impl_typed_array {
+  ($name:ident, $rust_type:ident, $typed_array_type:expr) => {
+    /// zero copy typed array shared between rust and napi
+    ///
+    /// Values received from JavaScript borrow the underlying `ArrayBuffer`,
+    /// values created from `Vec` are moved into a new `ArrayBuffer` without copying.
+    pub struct $name {
+      inner: TypedArrayData<$rust_type>,
+    }
+
+    impl $name {
+      pub fn new(data: Vec<$rust_type>) -> Self {
+        $name {
+          inner: TypedArrayData::Owned(data),
+        }
+      }
+    }
+
+    impl From<Vec<$rust_type>> for $name {
+      fn from(data: Vec<$rust_type>) -> Self {
+        $name::new(data)
+      }
+    }
+
+    impl From<&[$rust_type]> for $name {
+      fn from(data: &[$rust_type]) -> Self {
+        $name::new(data.to_vec())
+      }
+    }
+
+    impl From<$name> for Vec<$rust_type> {
+      fn from(mut typed_array: $name) -> Self {
+        match &mut typed_array.inner {
+          TypedArrayData::Owned(vec) => mem::take(vec),
+          TypedArrayData::Borrowed { .. } => typed_array.inner.as_slice().to_vec(),
+        }
+      }
+    }
+
+    impl AsRef<[$rust_type]> for $name {
+      fn as_ref(&self) -> &[$rust_type] {
+        self.inner.as_slice()
+      }
+    }
+
+    impl AsMut<[$rust_type]> for $name {
+      fn as_mut(&mut self) -> &mut [$rust_type] {
+        self.inner.as_mut_slice()
+      }
+    }
+
+    impl Deref for $name {
+      type Target = [$rust_type];
+
+      fn deref(&self) -> &[$rust_type] {
+        self.inner.as_slice()
+      }
+    }
+
+    impl DerefMut for $name {
+      fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.as_mut_slice()
+      }
+    }
+
+    impl TypeName for $name {
+      fn type_name() -> &'static str {
+        stringify!($name)
+      }
+
+      fn value_type() -> ValueType {
+        ValueType::Object
+      }
+    }
+
+    impl ValidateNapiValue for $name {
+      fn type_of() -> Vec<ValueType> {
+        vec![ValueType::Object]
+      }
+    }
+
+    impl FromNapiValue for $name {
+      unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        Ok($name {
+          inner: typed_array_from_napi_value(env, napi_val, $typed_array_type, stringify!($name))?,
+        })
+      }
+    }
+
+    impl ToNapiValue for $name {
+      unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        typed_array_to_napi_value(env, val.inner, $typed_array_type, stringify!($name))
+      }
+    }
+  };
+}
+
+impl_typed_array!(Int8Array, i8, TypedArrayType::Int8);
+impl_typed_array!(Uint8Array, u8, TypedArrayType::Uint8);
+impl_typed_array!(Uint8ClampedArray, u8, TypedArrayType::Uint8Clamped);
+impl_typed_array!(Int16Array, i16, TypedArrayType::Int16);
+impl_typed_array!(Uint16Array, u16, TypedArrayType::Uint16);
+impl_typed_array!(Int32Array, i32, TypedArrayType::Int32);
+impl_typed_array!(Uint32Array, u32, TypedArrayType::Uint32);
+impl_typed_array!(Float32Array, f32, TypedArrayType::Float32);
+impl_typed_array!(Float64Array, f64, TypedArrayType::Float64);
+#[cfg(feature = "napi6")]
+impl_typed_array!(BigInt64Array, i64, TypedArrayType::BigInt64);
+#[cfg(feature = "napi6")]
+impl_typed_array!(BigUint64Array, u64, TypedArrayType::BigUint64);
+
+/// Borrow the backing store of a `TypedArray` (or `Buffer` for `[u8]`) for the duration of a call.
+macro_rules! impl_typed_array_slice {
+  ($rust_type:ident, $type_name:literal, $($typed_array_type:expr),+) => {
+    impl FromNapiRef for [$rust_type] {
+      unsafe fn from_napi_ref(env: sys::napi_env, napi_val: sys::napi_value) -> Result<&'static Self> {
+        let (data, length) =
+          get_typed_array_info(env, napi_val, &[$($typed_array_type),+], $type_name)?;
+
+        if data.is_null() || length == 0 {
+          Ok(&[])
+        } else {
+          Ok(slice::from_raw_parts(data as *const $rust_type, length))
+        }
+      }
+    }
+
+    impl FromNapiMutRef for [$rust_type] {
+      unsafe fn from_napi_mut_ref(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+      ) -> Result<&'static mut Self> {
+        let (data, length) =
+          get_typed_array_info(env, napi_val, &[$($typed_array_type),+], $type_name)?;
+
+        if data.is_null() || length == 0 {
+          Ok(&mut [])
+        } else {
+          Ok(slice::from_raw_parts_mut(data as *mut $rust_type, length))
+        }
+      }
+    }
+  };
+}
+
+impl_typed_array_slice!(i8, "Int8Array", TypedArrayType::Int8);
+impl_typed_array_slice!(
+  u8,
+  "Uint8Array",
+  TypedArrayType::Uint8,
+  TypedArrayType::Uint8Clamped
+);
+impl_typed_array_slice!(i16, "Int16Array", TypedArrayType::Int16);
+impl_typed_array_slice!(u16, "Uint16Array", TypedArrayType::Uint16);
+impl_typed_array_slice!(i32, "Int32Array", TypedArrayType::Int32);
+impl_typed_array_slice!(u32, "Uint32Array", TypedArrayType::Uint32);
+impl_typed_array_slice!(f32, "Float32Array", TypedArrayType::Float32);
+impl_typed_array_slice!(f64, "Float64Array", TypedArrayType::Float64);
+#[cfg(feature = "napi6")]
+impl_typed_array_slice!(i64, "BigInt64Array", TypedArrayType::BigInt64);
+#[cfg(feature = "napi6")]
+impl_typed_array_slice!(u64, "BigUint64Array", TypedArrayType::BigUint64);
@@ -0,0 +1,259 @@
+use std::ptr;
+
+use crate::{bindgen_prelude::*, check_status, sys, Result, ValueType};
+
+/// JavaScript `BigInt` of any size
+///
+/// The `FromNapiValue` implementations of `u64`, `i128` and `u128` fail with `RangeError`
+/// if the `BigInt` doesn't fit, the `get_*` methods of `BigInt` truncate it instead,
+/// like `BigInt.asIntN` and `BigInt.asUintN`.
+///
+/// The values are compared without the leading zero words, and `-0` equals to `0`.
+#[derive(Debug, Clone, Default)]
+pub struct BigInt {
+  /// `true` if the value is negative
+  pub sign_bit: bool,
+  /// Words of the absolute value, the least significant word first
+  pub words: Vec<u64>,
+}
+
+impl BigInt {
+  fn new(sign_bit: bool, mut words: Vec<u64>) -> Self {
+    while words.last() == Some(&0) {
+      words.pop();
+    }
+    BigInt {
+      // -0n doesn't exist
+      sign_bit: sign_bit && !words.is_empty(),
+      words,
+    }
+  }
+
+  /// Words without the leading zero words
+  fn significant_words(&self) -> &[u64] {
+    let len = self
+      .words
+      .iter()
+      .rposition(|word| *word != 0)
+      .map_or(0, |index| index + 1);
+    &self.words[..len]
+  }
+
+  /// The lowest 128 bits of the absolute value, and whether the higher words are all zero
+  fn magnitude(&self) -> (u128, bool) {
+    let low = self.words.first().copied().unwrap_or(0) as u128;
+    let high = self.words.get(1).copied().unwrap_or(0) as u128;
+    let fits = self.words.iter().skip(2).all(|word| *word == 0);
+
+    ((high << 64) | low, fits)
+  }
+
+  /// (value, lossless)
+  ///
+  /// The value is truncated to 64 bits if it doesn't fit into `i64`
+  pub fn get_i64(&self) -> (i64, bool) {
+    let (value, lossless) = self.get_i128();
+    (value as i64, lossless && value as i64 as i128 == value)
+  }
+
+  /// (signed, value, lossless)
+  ///
+  /// The absolute value is truncated to 64 bits if it doesn't fit into `u64`
+  pub fn get_u64(&self) -> (bool, u64, bool) {
+    let (signed, value, lossless) = self.get_u128();
+    (signed, value as u64, lossless && value <= u64::MAX as u128)
+  }
+
+  /// (value, lossless)
+  ///
+  /// The value is truncated to 128 bits if it doesn't fit into `i128`
+  pub fn get_i128(&self) -> (i128, bool) {
+    let (magnitude, fits) = self.magnitude();
+    if self.sign_bit {
+      let lossless = fits && magnitude <= i128::MIN.unsigned_abs();
+      ((magnitude as i128).wrapping_neg(), lossless)
+    } else {
+      (magnitude as i128, fits && magnitude <= i128::MAX as u128)
+    }
+  }
+
+  /// (signed, value, lossless)
+  ///
+  /// The absolute value is truncated to 128 bits if it doesn't fit into `u128`
+  pub fn get_u128(&self) -> (bool, u128, bool) {
+    let (magnitude, fits) = self.magnitude();
+    (self.sign_bit, magnitude, fits)
+  }
+
+  fn checked_u64(&self) -> Option<u64> {
+    match self.get_u64() {
+      // -0n doesn't exist, so the negative values are never zero
+      (signed, value, true) if !signed || value == 0 => Some(value),
+      _ => None,
+    }
+  }
+
+  fn checked_i128(&self) -> Option<i128> {
+    match self.get_i128() {
+      (value, true) => Some(value),
+      _ => None,
+    }
+  }
+
+  fn checked_u128(&self) -> Option<u128> {
+    match self.get_u128() {
+      (signed, value, true) if !signed || value == 0 => Some(value),
+      _ => None,
+    }
+  }
+}
+
+impl PartialEq for BigInt {
+  fn eq(&self, other: &Self) -> bool {
+    let words = self.significant_words();
+    words == other.significant_words() && (self.sign_bit == other.sign_bit || words.is_empty())
+  }
+}
+
+impl Eq for BigInt {}
+
+impl From<i64> for BigInt {
+  fn from(value: i64) -> Self {
+    BigInt::from(value as i128)
+  }
+}
+
+impl From<u64> for BigInt {
+  fn from(value: u64) -> Self {
+    BigInt::new(false, vec![value])
+  }
+}
+
+impl From<i128> for BigInt {
+  fn from(value: i128) -> Self {
+    let magnitude = value.unsigned_abs();
+    BigInt::new(value < 0, vec![magnitude as u64, (magnitude >> 64) as u64])
+  }
+}
+
+impl From<u128> for BigInt {
+  fn from(value: u128) -> Self {
+    BigInt::new(false, vec![value as u64, (value >> 64) as u64])
+  }
+}
+
+fn out_of_range(type_name: &str) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!("BigInt is out of range of rust type `{}`", type_name),
+  )
+  .with_kind(ErrorKind::RangeError)
+}
+
+impl TypeName for BigInt {
+  fn type_name() -> &'static str {
+    "BigInt"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Bigint
+  }
+}
+
+impl ValidateNapiValue for BigInt {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Bigint]
+  }
+}
+
+impl FromNapiValue for BigInt {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut word_count = 0;
+    check_status!(
+      sys::napi_get_value_bigint_words(
+        env,
+        napi_val,
+        ptr::null_mut(),
+        &mut word_count,
+        ptr::null_mut(),
+      ),
+      "Failed to convert napi value into rust type `BigInt`"
+    )?;
+
+    let mut sign_bit = 0;
+    let mut words = vec![0; word_count];
+    check_status!(
+      sys::napi_get_value_bigint_words(
+        env,
+        napi_val,
+        &mut sign_bit,
+        &mut word_count,
+        words.as_mut_ptr(),
+      ),
+      "Failed to get words of BigInt"
+    )?;
+    words.truncate(word_count);
+
+    Ok(BigInt::new(sign_bit == 1, words))
+  }
+}
+
+impl ToNapiValue for BigInt {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut ret = ptr::null_mut();
+    check_status!(
+      sys::napi_create_bigint_words(
+        env,
+        val.sign_bit as i32,
+        val.words.len(),
+        val.words.as_ptr(),
+        &mut ret,
+      ),
+      "Failed to convert rust type `BigInt` into napi value"
+    )?;
+
+    Ok(ret)
+  }
+}
+
+macro_rules! impl_bigint_conversions {
+  ($( ($name:literal, $t:ty, $checked:ident) ,)*) => {
+    $(
+      impl TypeName for $t {
+        fn type_name() -> &'static str {
+          $name
+        }
+
+        fn value_type() -> ValueType {
+          ValueType::Bigint
+        }
+      }
+
+      impl ValidateNapiValue for $t {
+        fn type_of() -> Vec<ValueType> {
+          vec![ValueType::Bigint]
+        }
+      }
+
+      impl FromNapiValue for $t {
+        unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+          BigInt::from_napi_value(env, napi_val)?
+            .$checked()
+            .ok_or_else(|| out_of_range($name))
+        }
+      }
+
+      impl ToNapiValue for $t {
+        unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+          BigInt::to_napi_value(env, BigInt::from(val))
+        }
+      }
+    )*
+  };
+}
+
+impl_bigint_conversions!(
+  ("u64", u64, checked_u64),
+  ("i128", i128, checked_i128),
+  ("u128", u128, checked_u128),
+);
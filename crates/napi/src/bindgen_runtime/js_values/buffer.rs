@@ -1,18 +1,24 @@
+use std::mem;
 use std::ops::{Deref, DerefMut};
-use std::{mem, ptr};
+use std::ptr;
+use std::slice;
 
 use crate::{bindgen_prelude::*, check_status, sys, Result, ValueType};
 
-/// zero copy u8 vector shared between rust and napi
+/// u8 vector allocated by Rust, which is moved into JavaScript without copying
+///
+/// As an argument, the whole content of the JavaScript `Buffer` is copied into a new `Vec<u8>`,
+/// so it could be sent to other threads, e.g. used in `async fn`.
+/// The copy costs `O(n)` time and memory for every call, and returning it to JavaScript
+/// creates a new `Buffer` rather than the one passed in.
+/// Use `BufferRef` to access the memory of the JavaScript `Buffer` without copying.
 pub struct Buffer {
-  inner: mem::ManuallyDrop<Vec<u8>>,
+  inner: Vec<u8>,
 }
 
 impl From<Vec<u8>> for Buffer {
   fn from(data: Vec<u8>) -> Self {
-    Buffer {
-      inner: mem::ManuallyDrop::new(data),
-    }
+    Buffer { inner: data }
   }
 }
 
@@ -22,6 +28,12 @@ impl From<&[u8]> for Buffer {
   }
 }
 
+impl From<Buffer> for Vec<u8> {
+  fn from(buf: Buffer) -> Self {
+    buf.inner
+  }
+}
+
 impl AsRef<[u8]> for Buffer {
   fn as_ref(&self) -> &[u8] {
     self.inner.as_slice()
@@ -58,33 +70,50 @@ impl TypeName for Buffer {
   }
 }
 
+/// Memory of the JavaScript `Buffer`, `data` is null if it's empty
+unsafe fn get_buffer_info(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+) -> Result<(*mut u8, usize)> {
+  let mut data = ptr::null_mut();
+  let mut length = 0;
+
+  check_status!(
+    sys::napi_get_buffer_info(env, napi_val, &mut data, &mut length as *mut usize),
+    "Failed to convert napi buffer into rust Vec<u8>"
+  )?;
+
+  Ok((data as *mut u8, length))
+}
+
+unsafe fn buffer_slice<'a>(data: *const u8, length: usize) -> &'a [u8] {
+  if data.is_null() || length == 0 {
+    &[]
+  } else {
+    slice::from_raw_parts(data, length)
+  }
+}
+
 impl FromNapiValue for Buffer {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
-    let mut buf = ptr::null_mut();
-    let mut len = 0;
-
-    check_status!(
-      sys::napi_get_buffer_info(env, napi_val, &mut buf, &mut len as *mut usize),
-      "Failed to convert napi buffer into rust Vec<u8>"
-    )?;
+    let (data, length) = get_buffer_info(env, napi_val)?;
 
-    Ok(Self {
-      inner: mem::ManuallyDrop::new(Vec::from_raw_parts(buf as *mut _, len, len)),
-    })
+    Ok(Buffer::from(buffer_slice(data, length)))
   }
 }
 
 impl ToNapiValue for Buffer {
-  unsafe fn to_napi_value(env: sys::napi_env, mut val: Self) -> Result<sys::napi_value> {
-    let len = val.inner.len();
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut data = mem::ManuallyDrop::new(val.inner);
+    let len = data.len();
     let mut ret = ptr::null_mut();
     check_status!(
       sys::napi_create_external_buffer(
         env,
         len,
-        val.inner.as_mut_ptr() as *mut _,
+        data.as_mut_ptr() as *mut _,
         Some(drop_buffer),
-        Box::into_raw(Box::new((len, val.inner.capacity()))) as *mut _,
+        Box::into_raw(Box::new((len, data.capacity()))) as *mut _,
         &mut ret,
       ),
       "Failed to create napi buffer"
@@ -99,3 +128,109 @@ impl ValidateNapiValue for Buffer {
     vec![ValueType::Object]
   }
 }
+
+/// JavaScript `Buffer` accessed without copying
+///
+/// The source value is kept alive with a `napi_ref` until it's dropped,
+/// and the same JavaScript object is returned when it's passed back.
+/// It can't be sent to other threads, copy it out with `to_buffer` to use it in `async fn`.
+pub struct BufferRef {
+  env: sys::napi_env,
+  reference: sys::napi_ref,
+  data: *mut u8,
+  length: usize,
+}
+
+impl BufferRef {
+  /// Copy the content into a `Buffer` owned by Rust
+  pub fn to_buffer(&self) -> Buffer {
+    Buffer::from(self.as_ref())
+  }
+}
+
+impl Drop for BufferRef {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_delete_reference(self.env, self.reference) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Delete Buffer reference failed"
+    );
+  }
+}
+
+impl AsRef<[u8]> for BufferRef {
+  fn as_ref(&self) -> &[u8] {
+    unsafe { buffer_slice(self.data, self.length) }
+  }
+}
+
+impl AsMut<[u8]> for BufferRef {
+  fn as_mut(&mut self) -> &mut [u8] {
+    if self.data.is_null() || self.length == 0 {
+      &mut []
+    } else {
+      unsafe { slice::from_raw_parts_mut(self.data, self.length) }
+    }
+  }
+}
+
+impl Deref for BufferRef {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    self.as_ref()
+  }
+}
+
+impl DerefMut for BufferRef {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.as_mut()
+  }
+}
+
+impl TypeName for BufferRef {
+  fn type_name() -> &'static str {
+    "Buffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl FromNapiValue for BufferRef {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let (data, length) = get_buffer_info(env, napi_val)?;
+
+    let mut reference = ptr::null_mut();
+    check_status!(
+      sys::napi_create_reference(env, napi_val, 1, &mut reference),
+      "Failed to create reference of napi buffer"
+    )?;
+
+    Ok(BufferRef {
+      env,
+      reference,
+      data,
+      length,
+    })
+  }
+}
+
+impl ToNapiValue for BufferRef {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut ret = ptr::null_mut();
+    check_status!(
+      sys::napi_get_reference_value(env, val.reference, &mut ret),
+      "Failed to get napi buffer from reference"
+    )?;
+
+    Ok(ret)
+  }
+}
+
+impl ValidateNapiValue for BufferRef {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
@@ -0,0 +1,242 @@
+use std::ptr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{bindgen_prelude::*, check_status, sys, Result, ValueType};
+
+/// The time values of JavaScript `Date` are limited to ±100,000,000 days from the UNIX epoch
+const MAX_TIMESTAMP: f64 = 8.64e15;
+
+/// JavaScript `Date`, the milliseconds elapsed since the UNIX epoch
+///
+/// Converting an `Invalid Date` from JavaScript fails,
+/// as well as converting a time out of the range of JavaScript `Date` into JavaScript.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Date {
+  timestamp: f64,
+}
+
+impl Date {
+  /// `Date` of the milliseconds elapsed since the UNIX epoch,
+  /// fails if it's `NaN` or out of the range of JavaScript `Date`
+  pub fn from_timestamp(timestamp: f64) -> Result<Self> {
+    if timestamp.is_nan() {
+      return Err(
+        Error::new(
+          Status::InvalidArg,
+          "Invalid Date, the time value is NaN".to_owned(),
+        )
+        .with_kind(ErrorKind::RangeError),
+      );
+    }
+    if timestamp.abs() > MAX_TIMESTAMP {
+      return Err(
+        Error::new(
+          Status::InvalidArg,
+          format!("Time value {} is out of range of Date", timestamp),
+        )
+        .with_kind(ErrorKind::RangeError),
+      );
+    }
+
+    Ok(Date { timestamp })
+  }
+
+  /// The milliseconds elapsed since the UNIX epoch, like `Date.prototype.valueOf`
+  pub fn timestamp(&self) -> f64 {
+    self.timestamp
+  }
+}
+
+impl From<SystemTime> for Date {
+  fn from(time: SystemTime) -> Self {
+    let timestamp = match time.duration_since(UNIX_EPOCH) {
+      Ok(elapsed) => elapsed.as_secs_f64() * 1000.0,
+      Err(e) => -e.duration().as_secs_f64() * 1000.0,
+    };
+
+    Date { timestamp }
+  }
+}
+
+impl From<Date> for SystemTime {
+  fn from(date: Date) -> Self {
+    let elapsed = Duration::from_secs_f64(date.timestamp.abs() / 1000.0);
+    if date.timestamp >= 0.0 {
+      UNIX_EPOCH + elapsed
+    } else {
+      UNIX_EPOCH - elapsed
+    }
+  }
+}
+
+impl TypeName for Date {
+  fn type_name() -> &'static str {
+    "Date"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for Date {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl FromNapiValue for Date {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut is_date = false;
+    check_status!(
+      sys::napi_is_date(env, napi_val, &mut is_date),
+      "Failed to check if value is a Date"
+    )?;
+    if !is_date {
+      return Err(
+        Error::new(Status::DateExpected, "Expect value to be a Date".to_owned())
+          .with_kind(ErrorKind::TypeError),
+      );
+    }
+
+    let mut timestamp = 0f64;
+    check_status!(
+      sys::napi_get_date_value(env, napi_val, &mut timestamp),
+      "Failed to get time value of Date"
+    )?;
+
+    Date::from_timestamp(timestamp)
+  }
+}
+
+impl ToNapiValue for Date {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    // the `Date` converted from `SystemTime` is not checked
+    let date = Date::from_timestamp(val.timestamp)?;
+
+    let mut ret = ptr::null_mut();
+    check_status!(
+      sys::napi_create_date(env, date.timestamp, &mut ret),
+      "Failed to convert rust type `Date` into napi value"
+    )?;
+
+    Ok(ret)
+  }
+}
+
+impl TypeName for SystemTime {
+  fn type_name() -> &'static str {
+    "SystemTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for SystemTime {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl FromNapiValue for SystemTime {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Date::from_napi_value(env, napi_val).map(SystemTime::from)
+  }
+}
+
+impl ToNapiValue for SystemTime {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    Date::to_napi_value(env, Date::from(val))
+  }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_date {
+  use std::convert::TryFrom;
+
+  use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+  use super::*;
+
+  impl From<DateTime<Utc>> for Date {
+    fn from(time: DateTime<Utc>) -> Self {
+      Date {
+        timestamp: time.timestamp_millis() as f64,
+      }
+    }
+  }
+
+  /// `NaiveDateTime` is treated as UTC
+  impl From<NaiveDateTime> for Date {
+    fn from(time: NaiveDateTime) -> Self {
+      Date::from(Utc.from_utc_datetime(&time))
+    }
+  }
+
+  impl TryFrom<Date> for DateTime<Utc> {
+    type Error = Error;
+
+    /// Fails if the time is out of the range of `DateTime`, which is narrower than `Date`
+    fn try_from(date: Date) -> Result<Self> {
+      Utc
+        .timestamp_millis_opt(date.timestamp as i64)
+        .single()
+        .ok_or_else(|| {
+          Error::new(
+            Status::InvalidArg,
+            format!("Time value {} is out of range of DateTime", date.timestamp),
+          )
+          .with_kind(ErrorKind::RangeError)
+        })
+    }
+  }
+
+  impl TryFrom<Date> for NaiveDateTime {
+    type Error = Error;
+
+    fn try_from(date: Date) -> Result<Self> {
+      DateTime::<Utc>::try_from(date).map(|time| time.naive_utc())
+    }
+  }
+
+  macro_rules! impl_chrono_conversions {
+    ($( ($name:literal, $t:ty) ,)*) => {
+      $(
+        impl TypeName for $t {
+          fn type_name() -> &'static str {
+            $name
+          }
+
+          fn value_type() -> ValueType {
+            ValueType::Object
+          }
+        }
+
+        impl ValidateNapiValue for $t {
+          fn type_of() -> Vec<ValueType> {
+            vec![ValueType::Object]
+          }
+        }
+
+        impl FromNapiValue for $t {
+          unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+            <$t>::try_from(Date::from_napi_value(env, napi_val)?)
+          }
+        }
+
+        impl ToNapiValue for $t {
+          unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+            Date::to_napi_value(env, Date::from(val))
+          }
+        }
+      )*
+    };
+  }
+
+  impl_chrono_conversions!(
+    ("DateTime<Utc>", DateTime<Utc>),
+    ("NaiveDateTime", NaiveDateTime),
+  );
+}
@@ -0,0 +1,221 @@
+use std::any::{type_name, TypeId};
+use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+
+use crate::{bindgen_prelude::*, check_status, sys, Result, ValueType};
+
+/// Native value held by a JavaScript `external`, which is opaque to JavaScript.
+///
+/// The value is tagged with its rust type, so converting the `external` into
+/// `External<T>` fails with `TypeError` if it's created from another type.
+/// The tag is attached by `napi_type_tag_object` with `napi8`, otherwise it's stored
+/// in front of the value, which only works for the externals created by napi-rs.
+/// The value is moved into the `external` when it's converted into JavaScript,
+/// and dropped when the `external` is garbage collected.
+#[repr(C)]
+pub struct External<T: 'static> {
+  /// Must be the first field, it's read before the type of the value is known
+  #[cfg(not(feature = "napi8"))]
+  type_id: TypeId,
+  /// Bytes of the memory held by the value, reported to the JavaScript GC
+  size_hint: i64,
+  value: T,
+}
+
+impl<T: 'static> External<T> {
+  pub fn new(value: T) -> Self {
+    External::new_with_size_hint(value, 0)
+  }
+
+  /// `size_hint` is the bytes of memory kept alive by the `external`,
+  /// the GC is informed by `napi_adjust_external_memory` while the `external` is alive.
+  pub fn new_with_size_hint(value: T, size_hint: usize) -> Self {
+    External {
+      #[cfg(not(feature = "napi8"))]
+      type_id: TypeId::of::<External<T>>(),
+      size_hint: size_hint as i64,
+      value,
+    }
+  }
+
+  pub fn into_inner(self) -> T {
+    self.value
+  }
+}
+
+impl<T: 'static> Deref for External<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl<T: 'static> DerefMut for External<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.value
+  }
+}
+
+impl<T: 'static> AsRef<T> for External<T> {
+  fn as_ref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl<T: 'static> TypeName for External<T> {
+  fn type_name() -> &'static str {
+    "External"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::External
+  }
+}
+
+impl<T: 'static + Clone> ValidateNapiValue for External<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::External]
+  }
+}
+
+/// The value stays in the `external`, so it's cloned out of it
+impl<T: 'static + Clone> FromNapiValue for External<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let external = External::<T>::from_napi_ref(env, napi_val)?;
+    Ok(External::new_with_size_hint(
+      external.value.clone(),
+      external.size_hint as usize,
+    ))
+  }
+}
+
+impl<T: 'static> FromNapiRef for External<T> {
+  unsafe fn from_napi_ref(env: sys::napi_env, napi_val: sys::napi_value) -> Result<&'static Self> {
+    assert_type_of!(env, napi_val, ValueType::External)?;
+
+    #[cfg(feature = "napi8")]
+    let is_external_of_t = {
+      let mut is_external_of_t = false;
+      let type_tag = external_type_tag::<T>();
+      check_status!(
+        sys::napi_check_object_type_tag(env, napi_val, &type_tag, &mut is_external_of_t),
+        "Failed to check type tag of External"
+      )?;
+      is_external_of_t
+    };
+
+    let mut data = ptr::null_mut();
+    check_status!(
+      sys::napi_get_value_external(env, napi_val, &mut data),
+      "Failed to get value of External"
+    )?;
+
+    // all the externals created by napi-rs start with the `TypeId`,
+    // the externals created by other addons could be anything though
+    #[cfg(not(feature = "napi8"))]
+    let is_external_of_t =
+      !data.is_null() && *(data as *const TypeId) == TypeId::of::<External<T>>();
+
+    if !is_external_of_t || data.is_null() {
+      return Err(
+        Error::new(
+          Status::InvalidArg,
+          format!(
+            "Expect value to be an External of rust type `{}`",
+            type_name::<T>()
+          ),
+        )
+        .with_kind(ErrorKind::TypeError),
+      );
+    }
+
+    Ok(&*(data as *const External<T>))
+  }
+}
+
+impl<T: 'static> ToNapiValue for External<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let size_hint = val.size_hint;
+    let data = Box::into_raw(Box::new(val));
+
+    let mut ret = ptr::null_mut();
+    if let Err(e) = check_status!(
+      sys::napi_create_external(
+        env,
+        data as *mut c_void,
+        Some(finalize_external::<T>),
+        ptr::null_mut(),
+        &mut ret,
+      ),
+      "Failed to convert rust type `External` into napi value"
+    ) {
+      drop(Box::from_raw(data));
+      return Err(e);
+    }
+
+    #[cfg(feature = "napi8")]
+    {
+      let type_tag = external_type_tag::<T>();
+      check_status!(
+        sys::napi_type_tag_object(env, ret, &type_tag),
+        "Failed to tag type of External"
+      )?;
+    }
+
+    if size_hint > 0 {
+      let mut adjusted = 0i64;
+      if let Err(e) = check_status!(
+        sys::napi_adjust_external_memory(env, size_hint, &mut adjusted),
+        "Failed to adjust external memory of External"
+      ) {
+        // the `external` owns the value now, don't let the finalizer subtract the memory never added
+        (*data).size_hint = 0;
+        return Err(e);
+      }
+    }
+
+    Ok(ret)
+  }
+}
+
+/// Tag of `External<T>`, which is different for the same rust type in other addons
+#[cfg(feature = "napi8")]
+fn external_type_tag<T: 'static>() -> sys::napi_type_tag {
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::{Hash, Hasher};
+
+  static ADDON_TAG: u8 = 0;
+
+  let mut hasher = DefaultHasher::new();
+  TypeId::of::<External<T>>().hash(&mut hasher);
+
+  sys::napi_type_tag {
+    lower: hasher.finish(),
+    upper: &ADDON_TAG as *const u8 as u64,
+  }
+}
+
+unsafe extern "C" fn finalize_external<T: 'static>(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let external = Box::from_raw(finalize_data as *mut External<T>);
+  let size_hint = external.size_hint;
+  // panics can't be thrown in finalizers, they are only reported by the panic hook
+  let _ = catch_panic(|| {
+    drop(external);
+    Ok(())
+  });
+
+  if size_hint > 0 {
+    let mut adjusted = 0i64;
+    let status = sys::napi_adjust_external_memory(env, -size_hint, &mut adjusted);
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Calling napi_adjust_external_memory failed"
+    );
+  }
+}
@@ -0,0 +1,203 @@
+use std::marker::PhantomData;
+use std::ptr;
+
+use crate::{
+  assert_type_of, bindgen_prelude::*, check_pending_exception, check_status, sys, JsUnknown,
+  Result, ValueType,
+};
+
+/// Arguments of `Function`, the tuples of `ToNapiValue` values.
+///
+/// `()` is no argument, `(T,)` is a single argument.
+pub trait FunctionArgs {
+  /// # Safety
+  ///
+  /// `env` must be the env of the current napi callback
+  unsafe fn into_napi_values(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>>;
+}
+
+impl FunctionArgs for () {
+  unsafe fn into_napi_values(self, _env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+    Ok(vec![])
+  }
+}
+
+macro_rules! impl_function_args {
+  ( $( ( $($arg:ident),+ ) ,)* ) => {
+    $(
+      impl<$($arg: ToNapiValue),+> FunctionArgs for ($($arg,)+) {
+        #[allow(non_snake_case)]
+        unsafe fn into_napi_values(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+          let ($($arg,)+) = self;
+          Ok(vec![$($arg::to_napi_value(env, $arg)?),+])
+        }
+      }
+    )*
+  };
+}
+
+impl_function_args!(
+  (A),
+  (A, B),
+  (A, B, C),
+  (A, B, C, D),
+  (A, B, C, D, E),
+  (A, B, C, D, E, F),
+  (A, B, C, D, E, F, G),
+  (A, B, C, D, E, F, G, H),
+);
+
+/// JavaScript function, called with the arguments of tuple `Args` and returns `Return`.
+///
+/// The handle is only valid in the napi callback it's received in,
+/// use `create_ref` to keep the function across callbacks.
+///
+/// ```ignore
+/// #[napi]
+/// fn call_add(add: Function<(u32, u32), u32>) -> Result<u32> {
+///   add.call((), (1, 2))
+/// }
+/// ```
+pub struct Function<Args: FunctionArgs = (), Return: FromNapiValue = JsUnknown> {
+  env: sys::napi_env,
+  value: sys::napi_value,
+  _marker: PhantomData<fn(Args) -> Return>,
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> Clone for Function<Args, Return> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> Copy for Function<Args, Return> {}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> Function<Args, Return> {
+  /// Call the function with `this`, pass `()` for `undefined`.
+  ///
+  /// The exception thrown by the function is returned as the `Error`,
+  /// so it's rethrown unchanged if the error is returned to JavaScript.
+  pub fn call<This: ToNapiValue>(&self, this: This, args: Args) -> Result<Return> {
+    unsafe {
+      let this = This::to_napi_value(self.env, this)?;
+      let args = args.into_napi_values(self.env)?;
+
+      let mut ret = ptr::null_mut();
+      check_pending_exception!(
+        self.env,
+        sys::napi_call_function(
+          self.env,
+          this,
+          self.value,
+          args.len(),
+          args.as_ptr(),
+          &mut ret,
+        ),
+        "Failed to call function"
+      )?;
+
+      Return::from_napi_value(self.env, ret)
+    }
+  }
+
+  /// Call the function as a constructor, like `new F(...args)`
+  pub fn new_instance(&self, args: Args) -> Result<Object> {
+    unsafe {
+      let args = args.into_napi_values(self.env)?;
+
+      let mut ret = ptr::null_mut();
+      check_pending_exception!(
+        self.env,
+        sys::napi_new_instance(self.env, self.value, args.len(), args.as_ptr(), &mut ret),
+        "Failed to create instance"
+      )?;
+
+      Object::from_napi_value(self.env, ret)
+    }
+  }
+
+  /// Persistent reference of the function, which keeps it alive until the reference is dropped
+  pub fn create_ref(&self) -> Result<FunctionRef<Args, Return>> {
+    let mut reference = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(self.env, self.value, 1, &mut reference) },
+      "Failed to create reference of function"
+    )?;
+
+    Ok(FunctionRef {
+      env: self.env,
+      reference,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> TypeName for Function<Args, Return> {
+  fn type_name() -> &'static str {
+    "Function"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Function
+  }
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> ValidateNapiValue for Function<Args, Return> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Function]
+  }
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> FromNapiValue for Function<Args, Return> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    assert_type_of!(env, napi_val, ValueType::Function)?;
+
+    Ok(Function {
+      env,
+      value: napi_val,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> ToNapiValue for Function<Args, Return> {
+  unsafe fn to_napi_value(_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    Ok(val.value)
+  }
+}
+
+/// Persistent reference of `Function`, the reference is deleted on drop.
+///
+/// It could only be used and dropped on the JavaScript thread it's created on.
+pub struct FunctionRef<Args: FunctionArgs = (), Return: FromNapiValue = JsUnknown> {
+  env: sys::napi_env,
+  reference: sys::napi_ref,
+  _marker: PhantomData<fn(Args) -> Return>,
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> FunctionRef<Args, Return> {
+  /// The referenced function, which is valid in the current napi callback
+  pub fn borrow_back(&self) -> Result<Function<Args, Return>> {
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.env, self.reference, &mut value) },
+      "Failed to get referenced function"
+    )?;
+
+    Ok(Function {
+      env: self.env,
+      value,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> Drop for FunctionRef<Args, Return> {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_delete_reference(self.env, self.reference) };
+    debug_assert!(
+      status == sys::Status::napi_ok,
+      "Delete reference of function failed"
+    );
+  }
+}
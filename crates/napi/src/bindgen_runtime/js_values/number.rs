@@ -1,8 +1,53 @@
 use super::{check_status, sys, Result};
+use crate::{assert_type_of, Error, ErrorKind, Status, ValueType};
+
+/// Number of `napi_val`, the type is checked
+unsafe fn get_number(env: sys::napi_env, napi_val: sys::napi_value) -> Result<f64> {
+  assert_type_of!(env, napi_val, ValueType::Number)?;
+
+  let mut value = 0f64;
+  check_status!(
+    sys::napi_get_value_double(env, napi_val, &mut value),
+    "Failed to get value of napi number"
+  )?;
+
+  Ok(value)
+}
+
+fn range_error(reason: String) -> Error {
+  Error::new(Status::InvalidArg, reason).with_kind(ErrorKind::RangeError)
+}
+
+/// Check `napi_val` is an integer in `[min, max)` without truncating it,
+/// `napi_get_value_int32` and friends truncate the fractional part and wrap the overflowed value.
+unsafe fn validate_integer(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  type_name: &str,
+  min: f64,
+  max: f64,
+) -> Result<()> {
+  let value = get_number(env, napi_val)?;
+
+  if value.is_nan() || (value.is_finite() && value.fract() != 0.0) {
+    return Err(range_error(format!(
+      "Expect value to be an integer of rust type `{}`, but received {}",
+      type_name, value
+    )));
+  }
+  if value < min || value >= max {
+    return Err(range_error(format!(
+      "Value {} is out of range of rust type `{}`",
+      value, type_name
+    )));
+  }
+
+  Ok(())
+}
 
 macro_rules! impl_number_conversions {
-	( $( ($name:literal, $t:ty, $get:ident, $create:ident) ,)* ) => {
-		$(
+  ( $( ($name:literal, $t:ty, $napi_t:ty, $get:ident, $create:ident) ,)* ) => {
+    $(
       impl $crate::bindgen_prelude::TypeName for $t {
         #[inline(always)]
         fn type_name() -> &'static str {
@@ -14,10 +59,60 @@ macro_rules! impl_number_conversions {
         }
       }
 
-      impl $crate::bindgen_prelude::ValidateNapiValue for $t {
+      impl $crate::bindgen_prelude::ToNapiValue for $t {
         #[inline(always)]
-        fn type_of() -> Vec<$crate::ValueType> {
-          vec![$crate::ValueType::Number]
+        unsafe fn to_napi_value(env: $crate::sys::napi_env, val: $t) -> Result<$crate::sys::napi_value> {
+          let mut ptr = std::ptr::null_mut();
+
+          check_status!(
+            sys::$create(env, val as $napi_t, &mut ptr),
+            "Failed to convert rust type `{}` into napi value",
+            $name,
+          )?;
+
+          Ok(ptr)
+        }
+      }
+
+      impl $crate::bindgen_prelude::FromNapiValue for $t {
+        #[inline(always)]
+        unsafe fn from_napi_value(env: $crate::sys::napi_env, napi_val: $crate::sys::napi_value) -> Result<Self> {
+          let mut ret = 0 as $napi_t;
+
+          check_status!(
+            sys::$get(env, napi_val, &mut ret),
+            "Failed to convert napi value into rust type `{}`",
+            $name
+          )?;
+
+          Ok(ret as $t)
+        }
+      }
+    )*
+  };
+}
+
+impl_number_conversions!(
+  ("u32", u32, u32, napi_get_value_uint32, napi_create_uint32),
+  ("i32", i32, i32, napi_get_value_int32, napi_create_int32),
+  ("i64", i64, i64, napi_get_value_int64, napi_create_int64),
+  ("f32", f32, f64, napi_get_value_double, napi_create_double),
+  ("f64", f64, f64, napi_get_value_double, napi_create_double),
+);
+
+/// Without strict mode, the number is truncated toward zero and saturated to the range of
+/// the integer, `NaN` is converted into `0`, which is the `as` cast from `f64`
+macro_rules! impl_saturating_integer_conversions {
+  ( $( ($name:literal, $t:ty, $napi_t:ty, $create:ident) ,)* ) => {
+    $(
+      impl $crate::bindgen_prelude::TypeName for $t {
+        #[inline(always)]
+        fn type_name() -> &'static str {
+          $name
+        }
+
+        fn value_type() -> crate::ValueType {
+          crate::ValueType::Number
         }
       }
 
@@ -27,9 +122,9 @@ macro_rules! impl_number_conversions {
           let mut ptr = std::ptr::null_mut();
 
           check_status!(
-            sys::$create(env, val, &mut ptr),
-						"Failed to convert rust type `{}` into napi value",
-						$name,
+            sys::$create(env, val as $napi_t, &mut ptr),
+            "Failed to convert rust type `{}` into napi value",
+            $name,
           )?;
 
           Ok(ptr)
@@ -38,25 +133,84 @@ macro_rules! impl_number_conversions {
 
       impl $crate::bindgen_prelude::FromNapiValue for $t {
         #[inline(always)]
-				unsafe fn from_napi_value(env: $crate::sys::napi_env, napi_val: $crate::sys::napi_value) -> Result<Self> {
-					let mut ret = 0 as $t;
+        unsafe fn from_napi_value(env: $crate::sys::napi_env, napi_val: $crate::sys::napi_value) -> Result<Self> {
+          let mut ret = 0f64;
 
           check_status!(
-            sys::$get(env, napi_val, &mut ret),
-						"Failed to convert napi value into rust type `{}`",
+            sys::napi_get_value_double(env, napi_val, &mut ret),
+            "Failed to convert napi value into rust type `{}`",
             $name
           )?;
 
-					Ok(ret)
-				}
+          Ok(ret as $t)
+        }
       }
-		)*
-	};
+    )*
+  };
 }
 
-impl_number_conversions!(
-  ("u32", u32, napi_get_value_uint32, napi_create_uint32),
-  ("i32", i32, napi_get_value_int32, napi_create_int32),
-  ("i64", i64, napi_get_value_int64, napi_create_int64),
-  ("f64", f64, napi_get_value_double, napi_create_double),
+impl_saturating_integer_conversions!(
+  ("u8", u8, u32, napi_create_uint32),
+  ("i8", i8, i32, napi_create_int32),
+  ("u16", u16, u32, napi_create_uint32),
+  ("i16", i16, i32, napi_create_int32),
+  // `usize` above `i64::MAX` is rounded rather than wrapped into a negative number
+  ("usize", usize, f64, napi_create_double),
+  ("isize", isize, i64, napi_create_int64),
 );
+
+/// In strict mode, the integers are rejected with `RangeError`
+/// if the value is not an integer or it's out of range
+macro_rules! impl_validate_integer {
+  ( $( ($name:literal, $t:ty) ,)* ) => {
+    $(
+      impl $crate::bindgen_prelude::ValidateNapiValue for $t {
+        fn type_of() -> Vec<$crate::ValueType> {
+          vec![$crate::ValueType::Number]
+        }
+
+        unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+          // `MAX + 1` is exact for all the integers, the 64 bits `MAX` is rounded to it anyway
+          validate_integer(env, napi_val, $name, <$t>::MIN as f64, <$t>::MAX as f64 + 1.0)
+        }
+      }
+    )*
+  };
+}
+
+impl_validate_integer!(
+  ("u8", u8),
+  ("i8", i8),
+  ("u16", u16),
+  ("i16", i16),
+  ("u32", u32),
+  ("i32", i32),
+  ("i64", i64),
+  ("usize", usize),
+  ("isize", isize),
+);
+
+impl crate::bindgen_prelude::ValidateNapiValue for f32 {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Number]
+  }
+
+  /// `NaN` and infinities are representable by `f32`, only the finite values could overflow
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+    let value = get_number(env, napi_val)?;
+    if value.is_finite() && value.abs() > f32::MAX as f64 {
+      return Err(range_error(format!(
+        "Value {} is out of range of rust type `f32`",
+        value
+      )));
+    }
+
+    Ok(())
+  }
+}
+
+impl crate::bindgen_prelude::ValidateNapiValue for f64 {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Number]
+  }
+}
@@ -0,0 +1,213 @@
+use std::ffi::c_void;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use tokio::sync::oneshot::{channel, Receiver, Sender};
+
+use crate::{bindgen_prelude::*, check_pending_exception, check_status, sys, Result, ValueType};
+
+/// JavaScript `Promise` received from JavaScript, which could be awaited in Rust.
+///
+/// The `then` callbacks are attached when it's converted from the napi value,
+/// the settled value is converted into `T` on the JavaScript thread and sent to the future.
+/// A rejection is resolved into the `Error` holding the rejected value,
+/// so it's rethrown unchanged if the error is returned to JavaScript.
+pub struct Promise<T: FromNapiValue> {
+  value: Receiver<Result<T>>,
+}
+
+impl<T: FromNapiValue> TypeName for Promise<T> {
+  fn type_name() -> &'static str {
+    "Promise"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: FromNapiValue> ValidateNapiValue for Promise<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for Promise<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut is_promise = false;
+    check_status!(
+      sys::napi_is_promise(env, napi_val, &mut is_promise),
+      "Failed to check if value is a Promise"
+    )?;
+    if !is_promise {
+      return Err(
+        Error::new(
+          Status::InvalidArg,
+          "Expect value to be a Promise".to_owned(),
+        )
+        .with_kind(ErrorKind::TypeError),
+      );
+    }
+
+    let (sender, receiver) = channel();
+    attach_callbacks::<T>(env, napi_val, Arc::new(Mutex::new(Some(sender))))?;
+
+    Ok(Promise { value: receiver })
+  }
+}
+
+/// Shared by both callbacks, the first call takes the sender.
+///
+/// `then` could be overridden in JavaScript, so the callbacks may be called more than once,
+/// or never. The sender is dropped when both callbacks are garbage collected,
+/// which cancels the awaiting future if the `Promise` is never settled.
+type SharedSender<T> = Arc<Mutex<Option<Sender<Result<T>>>>>;
+
+unsafe fn attach_callbacks<T: FromNapiValue>(
+  env: sys::napi_env,
+  promise: sys::napi_value,
+  sender: SharedSender<T>,
+) -> Result<()> {
+  let mut then = ptr::null_mut();
+  check_status!(
+    sys::napi_get_named_property(env, promise, "then\0".as_ptr() as *const _, &mut then),
+    "Failed to get `then` of Promise"
+  )?;
+
+  let fulfilled_callback = create_callback(env, "onFulfilled", on_fulfilled::<T>, sender.clone())?;
+  let rejected_callback = create_callback(env, "onRejected", on_rejected::<T>, sender)?;
+
+  let args = [fulfilled_callback, rejected_callback];
+  let mut ret = ptr::null_mut();
+  check_pending_exception!(
+    env,
+    sys::napi_call_function(env, promise, then, 2, args.as_ptr(), &mut ret),
+    "Failed to call `then` of Promise"
+  )?;
+
+  Ok(())
+}
+
+unsafe fn create_callback<T: FromNapiValue>(
+  env: sys::napi_env,
+  name: &str,
+  callback: unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+  sender: SharedSender<T>,
+) -> Result<sys::napi_value> {
+  let data = Box::into_raw(Box::new(sender));
+
+  let mut js_callback = ptr::null_mut();
+  if let Err(e) = check_status!(
+    sys::napi_create_function(
+      env,
+      name.as_ptr() as *const _,
+      name.len(),
+      Some(callback),
+      data as *mut c_void,
+      &mut js_callback,
+    ),
+    "Failed to create the `{}` callback of Promise",
+    name,
+  ) {
+    drop(Box::from_raw(data));
+    return Err(e);
+  }
+
+  // the function is just created, so it's never wrapped by others
+  if let Err(e) = check_status!(
+    sys::napi_wrap(
+      env,
+      js_callback,
+      data as *mut c_void,
+      Some(finalize_sender::<T>),
+      ptr::null_mut(),
+      ptr::null_mut(),
+    ),
+    "Failed to attach the finalizer of `{}` callback of Promise",
+    name,
+  ) {
+    drop(Box::from_raw(data));
+    return Err(e);
+  }
+
+  Ok(js_callback)
+}
+
+unsafe extern "C" fn finalize_sender<T: FromNapiValue>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(Box::from_raw(finalize_data as *mut SharedSender<T>));
+}
+
+unsafe extern "C" fn on_fulfilled<T: FromNapiValue>(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  settle::<T, _>(env, callback_info, |value| T::from_napi_value(env, value))
+}
+
+unsafe extern "C" fn on_rejected<T: FromNapiValue>(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  settle::<T, _>(env, callback_info, |reason| {
+    Err(Error::from_js_exception(env, reason))
+  })
+}
+
+unsafe fn settle<T, F>(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+  settle_with: F,
+) -> sys::napi_value
+where
+  F: FnOnce(sys::napi_value) -> Result<T>,
+{
+  let mut argc = 1;
+  let mut value = ptr::null_mut();
+  let mut data = ptr::null_mut();
+  let status = sys::napi_get_cb_info(
+    env,
+    callback_info,
+    &mut argc,
+    &mut value,
+    ptr::null_mut(),
+    &mut data,
+  );
+  debug_assert!(
+    status == sys::Status::napi_ok,
+    "Get callback info of Promise failed"
+  );
+
+  let sender = &*(data as *const SharedSender<T>);
+  let sender = match sender.lock() {
+    Ok(mut sender) => sender.take(),
+    Err(poisoned) => poisoned.into_inner().take(),
+  };
+  // `None` if it's settled already, and the receiver is gone if the awaiting future is dropped
+  if let Some(sender) = sender {
+    let _ = sender.send(catch_panic(|| settle_with(value)));
+  }
+
+  ptr::null_mut()
+}
+
+impl<T: FromNapiValue> Future for Promise<T> {
+  type Output = Result<T>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    match Pin::new(&mut self.value).poll(cx) {
+      Poll::Pending => Poll::Pending,
+      Poll::Ready(Ok(value)) => Poll::Ready(value),
+      Poll::Ready(Err(_)) => Poll::Ready(Err(Error::new(
+        Status::Cancelled,
+        "Promise is dropped before it's settled".to_owned(),
+      ))),
+    }
+  }
+}
@@ -0,0 +1,53 @@
+use std::ops::{Deref, DerefMut};
+
+/// All the remaining arguments of a `#[napi]` function,
+/// must be the last argument.
+///
+/// ```ignore
+/// #[napi]
+/// fn sum(first: u32, rest: Rest<u32>) -> u32 {
+///   first + rest.iter().sum::<u32>()
+/// }
+/// ```
+pub struct Rest<T>(pub Vec<T>);
+
+impl<T> Rest<T> {
+  pub fn into_inner(self) -> Vec<T> {
+    self.0
+  }
+}
+
+impl<T> From<Vec<T>> for Rest<T> {
+  fn from(args: Vec<T>) -> Self {
+    Rest(args)
+  }
+}
+
+impl<T> From<Rest<T>> for Vec<T> {
+  fn from(rest: Rest<T>) -> Self {
+    rest.0
+  }
+}
+
+impl<T> Deref for Rest<T> {
+  type Target = Vec<T>;
+
+  fn deref(&self) -> &Vec<T> {
+    &self.0
+  }
+}
+
+impl<T> DerefMut for Rest<T> {
+  fn deref_mut(&mut self) -> &mut Vec<T> {
+    &mut self.0
+  }
+}
+
+impl<T> IntoIterator for Rest<T> {
+  type Item = T;
+  type IntoIter = std::vec::IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.into_iter()
+  }
+}
@@ -1,5 +1,9 @@
 use serde_json::{Map, Value};
 
+#[cfg(feature = "napi6")]
+use crate::bindgen_runtime::BigInt;
+#[cfg(feature = "napi6")]
+use crate::ErrorKind;
 use crate::{bindgen_runtime::Null, check_status, sys, type_of, Error, Result, Status, ValueType};
 
 use super::{FromNapiValue, Object, ToNapiValue};
@@ -15,11 +19,15 @@ impl ToNapiValue for Value {
         } else if n.is_f64() {
           f64::to_napi_value(env, n.as_f64().unwrap())
         } else {
+          // larger than `i64::MAX`
           let n = n.as_u64().unwrap();
-          if n > u32::MAX as u64 {
-            todo!("impl BigInt")
-          } else {
-            u32::to_napi_value(env, n as u32)
+          #[cfg(feature = "napi6")]
+          {
+            u64::to_napi_value(env, n)
+          }
+          #[cfg(not(feature = "napi6"))]
+          {
+            f64::to_napi_value(env, n as f64)
           }
         }
       }
@@ -56,7 +64,22 @@ impl FromNapiValue for Value {
         }
       }
       #[cfg(feature = "napi6")]
-      ValueType::Bigint => todo!(),
+      ValueType::Bigint => {
+        let bigint = BigInt::from_napi_value(env, napi_val)?;
+        match (bigint.get_i64(), bigint.get_u64()) {
+          ((n, true), _) => Value::from(n),
+          (_, (false, n, true)) => Value::from(n),
+          _ => {
+            return Err(
+              Error::new(
+                Status::InvalidArg,
+                "BigInt is out of range of serde_json Number".to_owned(),
+              )
+              .with_kind(ErrorKind::RangeError),
+            )
+          }
+        }
+      }
       _ => Value::Null,
     };
 
@@ -1,10 +1,16 @@
 use std::ffi::c_void;
+use std::future::Future;
+use std::pin::Pin;
 use std::ptr;
-use std::rc::Rc;
 use std::sync::atomic::{AtomicPtr, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
 
 use super::{FromNapiValue, ToNapiValue, TypeName};
-use crate::{async_work, check_status, Env, Error, JsError, JsObject, NapiValue, Status, Task};
+use crate::{
+  async_work, check_status, Env, Error, JsBoolean, JsError, JsFunction, JsObject, NapiValue,
+  Result, Status, Task,
+};
 
 pub struct AsyncTask<T: Task> {
   inner: T,
@@ -38,10 +44,98 @@ impl<T: Task> AsyncTask<T> {
 }
 
 /// https://developer.mozilla.org/zh-CN/docs/Web/API/AbortController
+///
+/// Cancels the `AsyncTask` created with it, or the `async fn` which takes it as argument.
+/// It could be sent to other threads, the futures of `async fn` could check `aborted()`
+/// or select on `cancelled()` to stop early.
+#[derive(Clone)]
 pub struct AbortSignal {
-  raw_work: Rc<AtomicPtr<napi_sys::napi_async_work__>>,
-  raw_deferred: Rc<AtomicPtr<napi_sys::napi_deferred__>>,
-  status: Rc<AtomicU8>,
+  raw_work: Arc<AtomicPtr<napi_sys::napi_async_work__>>,
+  raw_deferred: Arc<AtomicPtr<napi_sys::napi_deferred__>>,
+  status: Arc<AtomicU8>,
+  /// Wakers of the `cancelled()` futures
+  wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+impl AbortSignal {
+  /// Whether `abort()` is called on the `AbortController` of the signal
+  pub fn aborted(&self) -> bool {
+    self.status.load(Ordering::Acquire) == 2
+  }
+
+  /// Future resolved when the signal is aborted
+  pub fn cancelled(&self) -> Cancelled {
+    Cancelled {
+      signal: self.clone(),
+    }
+  }
+
+  /// Run `fut` until it's done or the signal is aborted.
+  ///
+  /// `fut` is dropped on abort, and the `AbortError` is returned instead.
+  pub fn abortable<T, F: Future<Output = Result<T>>>(self, fut: F) -> Abortable<F> {
+    Abortable {
+      fut: Box::pin(fut),
+      cancelled: self.cancelled(),
+    }
+  }
+
+  fn abort(&self) {
+    self.status.store(2, Ordering::Release);
+    for waker in self.wakers.lock().unwrap().drain(..) {
+      waker.wake();
+    }
+  }
+}
+
+/// The error which the aborted tasks are rejected with
+fn abort_error() -> Error {
+  Error::new(Status::Cancelled, "AbortError".to_owned())
+    .with_code("ABORT_ERR")
+    .with_property("name", "AbortError")
+}
+
+/// Future returned by `AbortSignal::cancelled`
+pub struct Cancelled {
+  signal: AbortSignal,
+}
+
+impl Future for Cancelled {
+  type Output = ();
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    if self.signal.aborted() {
+      return Poll::Ready(());
+    }
+
+    let mut wakers = self.signal.wakers.lock().unwrap();
+    if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+      wakers.push(cx.waker().clone());
+    }
+    // the signal may be aborted before the waker is registered
+    if self.signal.aborted() {
+      Poll::Ready(())
+    } else {
+      Poll::Pending
+    }
+  }
+}
+
+/// Future returned by `AbortSignal::abortable`
+pub struct Abortable<F> {
+  fut: Pin<Box<F>>,
+  cancelled: Cancelled,
+}
+
+impl<T, F: Future<Output = Result<T>>> Future for Abortable<F> {
+  type Output = Result<T>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    if Pin::new(&mut self.cancelled).poll(cx).is_ready() {
+      return Poll::Ready(Err(abort_error()));
+    }
+    self.fut.as_mut().poll(cx)
+  }
 }
 
 impl FromNapiValue for AbortSignal {
@@ -49,48 +143,104 @@ impl FromNapiValue for AbortSignal {
     env: napi_sys::napi_env,
     napi_val: napi_sys::napi_value,
   ) -> crate::Result<Self> {
-    let mut signal = JsObject::from_raw_unchecked(env, napi_val);
-    let async_work_inner: Rc<AtomicPtr<napi_sys::napi_async_work__>> =
-      Rc::new(AtomicPtr::new(ptr::null_mut()));
-    let raw_promise: Rc<AtomicPtr<napi_sys::napi_deferred__>> =
-      Rc::new(AtomicPtr::new(ptr::null_mut()));
-    let task_status = Rc::new(AtomicU8::new(0));
-    let abort_controller = AbortSignal {
-      raw_work: async_work_inner.clone(),
-      raw_deferred: raw_promise.clone(),
-      status: task_status.clone(),
+    let signal = JsObject::from_raw_unchecked(env, napi_val);
+    let abort_signal = AbortSignal {
+      raw_work: Arc::new(AtomicPtr::new(ptr::null_mut())),
+      raw_deferred: Arc::new(AtomicPtr::new(ptr::null_mut())),
+      status: Arc::new(AtomicU8::new(0)),
+      wakers: Default::default(),
     };
+    // the `abort` event is never fired if the signal is aborted already
+    if signal
+      .get_named_property::<JsBoolean>("aborted")?
+      .get_value()?
+    {
+      abort_signal.status.store(2, Ordering::Release);
+      return Ok(abort_signal);
+    }
+
+    // every call listens to the signal with its own state, so the same signal could be passed
+    // to many calls, and the `onabort` handler of the signal is kept
+    let add_event_listener = signal
+      .get_named_property::<JsFunction>("addEventListener")
+      .map_err(|_| {
+        Error::new(
+          Status::InvalidArg,
+          "Expect value to be an AbortSignal".to_owned(),
+        )
+      })?;
     let js_env = Env::from_raw(env);
-    check_status!(napi_sys::napi_wrap(
+    let listener = create_abort_listener(env, abort_signal.clone())?;
+    let mut options = js_env.create_object()?;
+    options.set_named_property("once", js_env.get_boolean(true)?)?;
+    add_event_listener.call(
+      Some(&signal),
+      &[
+        js_env.create_string("abort")?.into_unknown(),
+        listener.into_unknown(),
+        options.into_unknown(),
+      ],
+    )?;
+
+    Ok(abort_signal)
+  }
+}
+
+/// The listener of the `abort` event, the state is freed when the listener is garbage collected
+unsafe fn create_abort_listener(
+  env: napi_sys::napi_env,
+  abort_signal: AbortSignal,
+) -> Result<JsFunction> {
+  let data = Box::into_raw(Box::new(abort_signal));
+
+  let mut listener = ptr::null_mut();
+  if let Err(e) = check_status!(
+    napi_sys::napi_create_function(
+      env,
+      "onAbort".as_ptr() as *const _,
+      7,
+      Some(on_abort),
+      data as *mut c_void,
+      &mut listener,
+    ),
+    "Failed to create the listener of AbortSignal"
+  ) {
+    drop(Box::from_raw(data));
+    return Err(e);
+  }
+
+  // the function is just created, so it's never wrapped by others
+  if let Err(e) = check_status!(
+    napi_sys::napi_wrap(
       env,
-      signal.0.value,
-      Box::into_raw(Box::new(abort_controller)) as *mut _,
+      listener,
+      data as *mut c_void,
       Some(async_task_abort_controller_finalize),
       ptr::null_mut(),
       ptr::null_mut(),
-    ))?;
-    signal.set_named_property("onabort", js_env.create_function("onabort", on_abort)?)?;
-    Ok(AbortSignal {
-      raw_work: async_work_inner,
-      raw_deferred: raw_promise,
-      status: task_status,
-    })
+    ),
+    "Failed to attach the finalizer of AbortSignal listener"
+  ) {
+    drop(Box::from_raw(data));
+    return Err(e);
   }
+
+  Ok(JsFunction::from_raw_unchecked(env, listener))
 }
 
 extern "C" fn on_abort(
   env: napi_sys::napi_env,
   callback_info: napi_sys::napi_callback_info,
 ) -> napi_sys::napi_value {
-  let mut this = ptr::null_mut();
+  let mut data = ptr::null_mut();
   unsafe {
     let get_cb_info_status = napi_sys::napi_get_cb_info(
       env,
       callback_info,
       &mut 0,
       ptr::null_mut(),
-      &mut this,
       ptr::null_mut(),
+      &mut data,
     );
     debug_assert_eq!(
       get_cb_info_status,
@@ -98,27 +248,22 @@ extern "C" fn on_abort(
       "{}",
       "Get callback info in AbortController abort callback failed"
     );
-    let mut async_task = ptr::null_mut();
-    let status = napi_sys::napi_unwrap(env, this, &mut async_task);
-    debug_assert_eq!(
-      status,
-      napi_sys::Status::napi_ok,
-      "{}",
-      "Unwrap async_task from AbortSignal failed"
-    );
-    let abort_controller = Box::leak(Box::from_raw(async_task as *mut AbortSignal));
+    let abort_controller = &*(data as *const AbortSignal);
     // Task Completed, return now
     if abort_controller.status.load(Ordering::Relaxed) == 1 {
       return ptr::null_mut();
     }
+    // abort function must be called from JavaScript main thread
+    abort_controller.abort();
     let raw_async_work = abort_controller.raw_work.load(Ordering::Relaxed);
+    // the signal is not used by `AsyncTask`, the `async fn` rejects itself
+    if raw_async_work.is_null() {
+      return ptr::null_mut();
+    }
     let deferred = abort_controller.raw_deferred.load(Ordering::Relaxed);
     napi_sys::napi_cancel_async_work(env, raw_async_work);
-    // abort function must be called from JavaScript main thread, so Relaxed Ordering is ok.
-    abort_controller.status.store(2, Ordering::Relaxed);
-    let abort_error = Error::new(Status::Cancelled, "AbortError".to_owned());
     let reject_status =
-      napi_sys::napi_reject_deferred(env, deferred, JsError::from(abort_error).into_value(env));
+      napi_sys::napi_reject_deferred(env, deferred, JsError::from(abort_error()).into_value(env));
     debug_assert_eq!(
       reject_status,
       napi_sys::Status::napi_ok,
@@ -135,6 +280,26 @@ impl<T: Task> ToNapiValue for AsyncTask<T> {
     val: Self,
   ) -> crate::Result<napi_sys::napi_value> {
     if let Some(abort_controller) = val.abort_signal {
+      // the work is not queued at all, the `abort` event which cancels it is never fired again
+      if abort_controller.aborted() {
+        let mut task = val.inner;
+        let mut deferred = ptr::null_mut();
+        let mut promise = ptr::null_mut();
+        check_status!(
+          napi_sys::napi_create_promise(env, &mut deferred, &mut promise),
+          "Failed to create the Promise of AsyncTask"
+        )?;
+        check_status!(
+          napi_sys::napi_reject_deferred(
+            env,
+            deferred,
+            JsError::from(abort_error()).into_value(env)
+          ),
+          "Failed to reject the Promise of AsyncTask"
+        )?;
+        task.finally(Env::from_raw(env))?;
+        return Ok(promise);
+      }
       let async_promise = async_work::run(env, val.inner, Some(abort_controller.status.clone()))?;
       abort_controller
         .raw_work
@@ -155,5 +320,5 @@ unsafe extern "C" fn async_task_abort_controller_finalize(
   finalize_data: *mut c_void,
   _finalize_hint: *mut c_void,
 ) {
-  Box::from_raw(finalize_data as *mut AbortSignal);
+  drop(Box::from_raw(finalize_data as *mut AbortSignal));
 }
@@ -0,0 +1,115 @@
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+
+use crate::{bindgen_prelude::*, check_status, sys, type_of, Result, ValueType};
+
+/// The JavaScript `this` of the function call, it's not counted in the JavaScript arguments.
+///
+/// ```ignore
+/// #[napi]
+/// fn get_name(this: This<Object>) -> Result<Option<String>> {
+///   this.get("name")
+/// }
+/// ```
+pub struct This<T = Object>(pub T);
+
+impl<T> This<T> {
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T> Deref for This<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T> DerefMut for This<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.0
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for This<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    T::from_napi_value(env, napi_val).map(This)
+  }
+}
+
+/// The JavaScript `new.target` of the function call, it's not counted in the JavaScript arguments.
+///
+/// It's the constructor invoked by `new`, which is a subclass when constructed with `super()`.
+pub struct NewTarget {
+  env: sys::napi_env,
+  inner: Option<sys::napi_value>,
+}
+
+impl NewTarget {
+  pub(crate) fn new(env: sys::napi_env, inner: sys::napi_value) -> Self {
+    NewTarget {
+      env,
+      inner: if inner.is_null() { None } else { Some(inner) },
+    }
+  }
+
+  /// Whether the function is called with `new`
+  pub fn is_constructor_call(&self) -> bool {
+    self.inner.is_some()
+  }
+
+  /// `new.target.name`
+  pub fn name(&self) -> Result<Option<String>> {
+    let target = match self.inner {
+      Some(target) => target,
+      None => return Ok(None),
+    };
+
+    let mut name = ptr::null_mut();
+    unsafe {
+      check_status!(
+        sys::napi_get_named_property(self.env, target, "name\0".as_ptr() as *const _, &mut name),
+        "Failed to get name of new.target"
+      )?;
+
+      if type_of!(self.env, name)? != ValueType::String {
+        return Ok(None);
+      }
+
+      String::from_napi_value(self.env, name).map(Some)
+    }
+  }
+
+  /// Whether `new.target` is the class generated by `T` itself, rather than a subclass of it
+  pub fn is_class<T: TypeName>(&self) -> Result<bool> {
+    let target = match self.inner {
+      Some(target) => target,
+      None => return Ok(false),
+    };
+
+    unsafe {
+      let constructor = match get_class_constructor(self.env, T::type_name()) {
+        Some(constructor) => constructor,
+        None => return Ok(false),
+      };
+
+      let mut constructor_value = ptr::null_mut();
+      check_status!(
+        sys::napi_get_reference_value(self.env, constructor, &mut constructor_value),
+        "Failed to get constructor of class `{}`",
+        T::type_name(),
+      )?;
+
+      let mut is_class = false;
+      check_status!(
+        sys::napi_strict_equals(self.env, target, constructor_value, &mut is_class),
+        "Failed to compare new.target with class `{}`",
+        T::type_name(),
+      )?;
+
+      Ok(is_class)
+    }
+  }
+}
@@ -1,14 +1,19 @@
+mod borrow;
 mod callback_info;
 mod env;
 mod error;
 mod js_values;
 mod module_register;
+mod panic;
 
+pub use borrow::*;
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
+pub use error::*;
 pub use js_values::*;
 pub use module_register::*;
+pub use panic::*;
 
 use super::sys;
 use std::{ffi::c_void, mem};
@@ -22,7 +27,11 @@ pub unsafe extern "C" fn raw_finalize_unchecked<T>(
   finalize_hint: *mut c_void,
 ) {
   let obj = finalize_data as *mut T;
-  Box::from_raw(obj);
+  // panics can't be thrown in finalizers, they are only reported by the panic hook
+  let _ = catch_panic(|| {
+    drop(Box::from_raw(obj));
+    Ok(())
+  });
   if !finalize_hint.is_null() {
     let size_hint = *Box::from_raw(finalize_hint as *mut Option<i64>);
     if let Some(changed) = size_hint {
@@ -1,51 +1,433 @@
-use std::{cell::RefCell, collections::HashMap, ffi::CString, ptr};
+use std::{collections::HashMap, ffi::CString, ptr, sync::Mutex};
 
-use crate::{check_status, check_status_or_throw, sys, JsError, Property, Result};
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+  check_status, error::DroppedExceptions, sys, Error, JsError, Property, Result, Status,
+};
 
 pub type ExportRegisterCallback = unsafe fn(sys::napi_env) -> Result<sys::napi_value>;
 pub type ModuleExportsCallback =
   unsafe fn(env: sys::napi_env, exports: sys::napi_value) -> Result<()>;
 
+// Registration data is collected by `#[ctor]` functions once per process,
+// and never consumed, so the module could be initialized in any number of envs and threads.
+static MODULE_REGISTER_CALLBACK: Mutex<Vec<(&'static str, ExportRegisterCallback)>> =
+  Mutex::new(Vec::new());
+static MODULE_CLASS_PROPERTIES: Mutex<
+  Vec<(
+    /* rust name */ &'static str,
+    /* js name */ &'static str,
+    Vec<Property>,
+  )>,
+> = Mutex::new(Vec::new());
+static MODULE_CLASS_PARENTS: Mutex<
+  Vec<(
+    /* rust name */ &'static str,
+    /* parent rust name */ &'static str,
+  )>,
+> = Mutex::new(Vec::new());
+// compatibility for #[module_exports]
+#[cfg(feature = "compat-mode")]
+static MODULE_EXPORTS: Mutex<Vec<ModuleExportsCallback>> = Mutex::new(Vec::new());
+
+/// Per env data of the bindgen runtime
+#[derive(Default)]
+pub(crate) struct EnvData {
+  pub(crate) classes: HashMap</* rust name */ &'static str, RegisteredClass>,
+  /// Constructors of the classes generated by `#[napi(error)]`
+  pub(crate) error_classes: HashMap</* js name */ &'static str, sys::napi_ref>,
+  pub(crate) dropped_exceptions: DroppedExceptions,
+  /// Data set by `Env::set_instance_data`, it shares the instance data slot with the runtime
+  #[cfg(feature = "napi6")]
+  pub(crate) instance_data: Option<InstanceData>,
+}
+
+pub(crate) struct RegisteredClass {
+  pub(crate) js_name: &'static str,
+  pub(crate) constructor: sys::napi_ref,
+  /// Rust names of the class and its subclasses, instances of them could be borrowed as this class
+  #[cfg(feature = "napi8")]
+  pub(crate) compatible_classes: Vec<&'static str>,
+}
+
+#[cfg(feature = "napi6")]
+pub(crate) struct InstanceData {
+  pub(crate) data: *mut std::ffi::c_void,
+  pub(crate) finalize_cb: sys::napi_finalize,
+  pub(crate) finalize_hint: *mut std::ffi::c_void,
+}
+
+impl EnvData {
+  unsafe fn cleanup(self, env: sys::napi_env) {
+    for (_, class) in self.classes {
+      sys::napi_delete_reference(env, class.constructor);
+    }
+    for (_, constructor) in self.error_classes {
+      sys::napi_delete_reference(env, constructor);
+    }
+    self.dropped_exceptions.release(env);
+  }
+}
+
+/// Run `f` with the `EnvData` of `env`, the data is created the first time it's accessed
+/// and dropped when the env is torn down.
+#[cfg(feature = "napi6")]
+pub(crate) unsafe fn with_env_data<R>(
+  env: sys::napi_env,
+  f: impl FnOnce(&mut EnvData) -> R,
+) -> Result<R> {
+  let mut data = ptr::null_mut();
+  check_status!(
+    sys::napi_get_instance_data(env, &mut data),
+    "Failed to get instance data"
+  )?;
+
+  if data.is_null() {
+    data = Box::into_raw(Box::new(EnvData::default())) as *mut std::ffi::c_void;
+    check_status!(
+      sys::napi_set_instance_data(env, data, Some(finalize_env_data), ptr::null_mut()),
+      "Failed to set instance data"
+    )?;
+  }
+
+  Ok(f(&mut *(data as *mut EnvData)))
+}
+
+#[cfg(feature = "napi6")]
+unsafe extern "C" fn finalize_env_data(
+  env: sys::napi_env,
+  data: *mut std::ffi::c_void,
+  _hint: *mut std::ffi::c_void,
+) {
+  let mut env_data = Box::from_raw(data as *mut EnvData);
+  let instance_data = env_data.instance_data.take();
+  env_data.cleanup(env);
+
+  if let Some(InstanceData {
+    data,
+    finalize_cb: Some(finalize_cb),
+    finalize_hint,
+  }) = instance_data
+  {
+    finalize_cb(env, data, finalize_hint);
+  }
+}
+
+#[cfg(not(feature = "napi6"))]
 thread_local! {
-  static MODULE_REGISTER_CALLBACK: RefCell<Vec<(&'static str, ExportRegisterCallback)>> = Default::default();
-  static MODULE_CLASS_PROPERTIES: RefCell<HashMap<&'static str, (&'static str, Vec<Property>)>> = Default::default();
-  static REGISTERED_CLASSES: RefCell<HashMap<
-    /* export name */ &'static str,
-    /* constructor */ sys::napi_ref,
-  >> = Default::default();
-  // compatibility for #[module_exports]
-  #[cfg(feature = "compat-mode")]
-  static MODULE_EXPORTS: std::cell::Cell<Vec<ModuleExportsCallback>> = Default::default();
+  // envs are bound to the thread they are created on
+  static ENV_DATA: std::cell::RefCell<HashMap<usize, EnvData>> = Default::default();
 }
 
-pub fn get_class_constructor(js_name: &'static str) -> Option<sys::napi_ref> {
-  REGISTERED_CLASSES.with(|registered_classes| {
-    let classes = registered_classes.borrow();
-    classes.get(js_name).copied()
+/// Run `f` with the `EnvData` of `env`, the data is created the first time it's accessed
+/// and dropped when the env is torn down.
+#[cfg(not(feature = "napi6"))]
+pub(crate) unsafe fn with_env_data<R>(
+  env: sys::napi_env,
+  f: impl FnOnce(&mut EnvData) -> R,
+) -> Result<R> {
+  let is_new = ENV_DATA.with(|env_data| !env_data.borrow().contains_key(&(env as usize)));
+
+  if is_new {
+    #[cfg(feature = "napi3")]
+    check_status!(
+      sys::napi_add_env_cleanup_hook(env, Some(cleanup_env_data), env as *mut std::ffi::c_void),
+      "Failed to add env cleanup hook"
+    )?;
+  }
+
+  Ok(ENV_DATA.with(|env_data| f(env_data.borrow_mut().entry(env as usize).or_default())))
+}
+
+#[cfg(all(feature = "napi3", not(feature = "napi6")))]
+unsafe extern "C" fn cleanup_env_data(env: *mut std::ffi::c_void) {
+  let env_data = ENV_DATA.with(|env_data| env_data.borrow_mut().remove(&(env as usize)));
+
+  if let Some(env_data) = env_data {
+    env_data.cleanup(env as sys::napi_env);
+  }
+}
+
+/// Constructor of the class generated by struct `rust_name` in `env`
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread
+pub unsafe fn get_class_constructor(
+  env: sys::napi_env,
+  rust_name: &'static str,
+) -> Option<sys::napi_ref> {
+  with_env_data(env, |env_data| {
+    env_data
+      .classes
+      .get(rust_name)
+      .map(|class| class.constructor)
   })
+  .ok()
+  .flatten()
 }
 
 #[cfg(feature = "compat-mode")]
 // compatibility for #[module_exports]
 pub fn register_module_exports(callback: ModuleExportsCallback) {
-  MODULE_EXPORTS.with(|cell| cell.set(vec![callback]));
+  let mut callbacks = MODULE_EXPORTS.lock().unwrap();
+  callbacks.clear();
+  callbacks.push(callback);
 }
 
 pub fn register_module_export(name: &'static str, cb: ExportRegisterCallback) {
-  MODULE_REGISTER_CALLBACK.with(|exports| {
-    let mut list = exports.borrow_mut();
-    list.push((name, cb));
-  });
+  MODULE_REGISTER_CALLBACK.lock().unwrap().push((name, cb));
 }
 
 pub fn register_class(rust_name: &'static str, js_name: &'static str, props: Vec<Property>) {
-  MODULE_CLASS_PROPERTIES.with(|map| {
-    let mut map = map.borrow_mut();
-    let val = map.entry(rust_name).or_default();
+  let mut classes = MODULE_CLASS_PROPERTIES.lock().unwrap();
+
+  match classes.iter_mut().find(|(name, _, _)| *name == rust_name) {
+    Some(class) => {
+      class.1 = js_name;
+      class.2.extend(props.into_iter());
+    }
+    None => classes.push((rust_name, js_name, props)),
+  }
+}
+
+/// Register the parent class of `#[napi(extends = Parent)]` struct
+pub fn register_class_parent(rust_name: &'static str, parent_rust_name: &'static str) {
+  MODULE_CLASS_PARENTS
+    .lock()
+    .unwrap()
+    .push((rust_name, parent_rust_name));
+}
+
+/// Methods and accessors of the parent classes.
+///
+/// `napi_define_class` checks the receiver of methods and accessors,
+/// so they can't be called with the subclass instances through the prototype chain,
+/// the subclass needs to define them again.
+fn get_inherited_properties(
+  classes: &HashMap<&'static str, (&'static str, Vec<Property>)>,
+  class_parents: &HashMap<&'static str, &'static str>,
+  rust_name: &'static str,
+  own_props: &[Property],
+) -> Vec<Property> {
+  let mut inherited_props: Vec<Property> = vec![];
+  let mut current = rust_name;
+
+  // the depth of inheritance is bounded to avoid looping forever on circular inheritance
+  for _ in 0..class_parents.len() {
+    let parent = match class_parents.get(current) {
+      Some(&parent) => parent,
+      None => break,
+    };
+
+    if let Some((_, parent_props)) = classes.get(parent) {
+      for prop in parent_props.iter() {
+        let overridden = own_props
+          .iter()
+          .chain(inherited_props.iter())
+          .any(|p| p.name == prop.name);
+
+        if !prop.is_ctor && !prop.is_static() && !overridden {
+          inherited_props.push(prop.clone());
+        }
+      }
+    }
+
+    current = parent;
+  }
+
+  inherited_props
+}
+
+/// Whether `rust_name` is `ancestor_rust_name` or inherits from it
+#[cfg(feature = "napi8")]
+fn is_subclass_of(
+  class_parents: &HashMap<&'static str, &'static str>,
+  rust_name: &'static str,
+  ancestor_rust_name: &'static str,
+) -> bool {
+  let mut current = rust_name;
+
+  // bounded like `get_inherited_properties`
+  for _ in 0..=class_parents.len() {
+    if current == ancestor_rust_name {
+      return true;
+    }
+    match class_parents.get(current) {
+      Some(&parent) => current = parent,
+      None => return false,
+    }
+  }
+
+  false
+}
+
+/// Make `class` extends `parent` in JavaScript, both the prototype chain of instances
+/// and the static members are inherited, equal to `class Class extends Parent {}`
+pub(crate) unsafe fn set_class_parent(
+  env: sys::napi_env,
+  class: sys::napi_value,
+  parent: sys::napi_value,
+) -> Result<()> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    sys::napi_get_global(env, &mut global),
+    "Failed to get global object"
+  )?;
+
+  let mut object_ctor = ptr::null_mut();
+  let object_name = CString::new("Object")?;
+  check_status!(
+    sys::napi_get_named_property(env, global, object_name.as_ptr(), &mut object_ctor),
+    "Failed to get global `Object`"
+  )?;
+
+  let mut set_prototype_of = ptr::null_mut();
+  let set_prototype_of_name = CString::new("setPrototypeOf")?;
+  check_status!(
+    sys::napi_get_named_property(
+      env,
+      object_ctor,
+      set_prototype_of_name.as_ptr(),
+      &mut set_prototype_of
+    ),
+    "Failed to get `Object.setPrototypeOf`"
+  )?;
+
+  let prototype_name = CString::new("prototype")?;
+  let mut class_prototype = ptr::null_mut();
+  check_status!(
+    sys::napi_get_named_property(env, class, prototype_name.as_ptr(), &mut class_prototype),
+    "Failed to get prototype of class"
+  )?;
+  let mut parent_prototype = ptr::null_mut();
+  check_status!(
+    sys::napi_get_named_property(env, parent, prototype_name.as_ptr(), &mut parent_prototype),
+    "Failed to get prototype of parent class"
+  )?;
+
+  for args in [[class_prototype, parent_prototype], [class, parent]] {
+    check_status!(
+      sys::napi_call_function(
+        env,
+        object_ctor,
+        set_prototype_of,
+        args.len(),
+        args.as_ptr(),
+        ptr::null_mut(),
+      ),
+      "Failed to set prototype of class"
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Define the registered classes on `exports` and store their constructors in the env data
+unsafe fn define_classes(env: sys::napi_env, exports: sys::napi_value) -> Result<()> {
+  let class_parents: HashMap<_, _> = MODULE_CLASS_PARENTS
+    .lock()
+    .unwrap()
+    .iter()
+    .copied()
+    .collect();
+  let to_register_classes: HashMap<_, _> = MODULE_CLASS_PROPERTIES
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|(rust_name, js_name, props)| (*rust_name, (*js_name, props.clone())))
+    .collect();
+  let mut defined_classes = HashMap::new();
+
+  for (&rust_name, (js_name, props)) in to_register_classes.iter() {
+    let js_name = *js_name;
+    let (ctor, mut props): (Vec<_>, Vec<_>) = props.iter().cloned().partition(|prop| prop.is_ctor);
+    // one or more or zero?
+    // zero is for `#[napi(task)]`
+    if ctor.is_empty() && props.is_empty() {
+      continue;
+    }
+    let inherited_props =
+      get_inherited_properties(&to_register_classes, &class_parents, rust_name, &props);
+    props.extend(inherited_props);
+    let ctor = ctor.get(0).map(|c| c.raw().method.unwrap()).unwrap_or(noop);
+    let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
+
+    let js_class_name = CString::new(js_name).unwrap();
+    let mut class_ptr = ptr::null_mut();
 
-    val.0 = js_name;
-    val.1.extend(props.into_iter());
-  });
+    check_status!(
+      sys::napi_define_class(
+        env,
+        js_class_name.as_ptr(),
+        js_name.len(),
+        Some(ctor),
+        ptr::null_mut(),
+        raw_props.len(),
+        raw_props.as_ptr(),
+        &mut class_ptr,
+      ),
+      "Failed to register class `{}` generate by struct `{}`",
+      &js_name,
+      &rust_name
+    )?;
+
+    let mut ctor_ref = ptr::null_mut();
+    sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
+
+    let class = RegisteredClass {
+      js_name,
+      constructor: ctor_ref,
+      #[cfg(feature = "napi8")]
+      compatible_classes: to_register_classes
+        .keys()
+        .copied()
+        .filter(|&name| is_subclass_of(&class_parents, name, rust_name))
+        .collect(),
+    };
+    with_env_data(env, |env_data| env_data.classes.insert(rust_name, class))?;
+    defined_classes.insert(rust_name, class_ptr);
+
+    check_status!(
+      sys::napi_set_named_property(env, exports, js_class_name.as_ptr(), class_ptr),
+      "Failed to register class `{}` generate by struct `{}`",
+      &js_name,
+      &rust_name
+    )?;
+  }
+
+  for (rust_name, parent_rust_name) in class_parents.into_iter() {
+    match (
+      defined_classes.get(rust_name),
+      defined_classes.get(parent_rust_name),
+    ) {
+      (Some(&class), Some(&parent)) => set_class_parent(env, class, parent)?,
+      _ => {
+        return Err(Error::new(
+          Status::GenericFailure,
+          format!(
+            "Failed to find parent class `{}` of `{}`",
+            parent_rust_name, rust_name
+          ),
+        ))
+      }
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+static TOKIO_RT_ENV_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// The tokio runtime is shared by all envs, shut it down with the last one
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+unsafe extern "C" fn cleanup_tokio_rt(arg: *mut std::ffi::c_void) {
+  if TOKIO_RT_ENV_COUNT.fetch_sub(1, Ordering::SeqCst) == 1 {
+    crate::shutdown_tokio_rt(arg);
+  }
 }
 
 #[no_mangle]
@@ -53,92 +435,43 @@ unsafe extern "C" fn napi_register_module_v1(
   env: sys::napi_env,
   exports: sys::napi_value,
 ) -> sys::napi_value {
-  MODULE_REGISTER_CALLBACK.with(|to_register_exports| {
-    to_register_exports
-      .take()
-      .into_iter()
-      .for_each(|(name, callback)| {
-        let js_name = CString::new(name).unwrap();
-        unsafe {
-          if let Err(e) = callback(env).and_then(|v| {
-            check_status!(
-              sys::napi_set_named_property(env, exports, js_name.as_ptr(), v),
-              "Failed to register export `{}`",
-              name,
-            )
-          }) {
-            JsError::from(e).throw_into(env)
-          }
-        }
-      })
-  });
-
-  MODULE_CLASS_PROPERTIES.with(|to_register_classes| {
-    for (rust_name, (js_name, props)) in to_register_classes.take().into_iter() {
-      unsafe {
-        let (ctor, props): (Vec<_>, Vec<_>) = props.into_iter().partition(|prop| prop.is_ctor);
-        // one or more or zero?
-        // zero is for `#[napi(task)]`
-        if ctor.is_empty() && props.is_empty() {
-          continue;
-        }
-        let ctor = ctor.get(0).map(|c| c.raw().method.unwrap()).unwrap_or(noop);
-        let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
-
-        let js_class_name = CString::new(js_name).unwrap();
-        let mut class_ptr = ptr::null_mut();
-
-        check_status_or_throw!(
-          env,
-          sys::napi_define_class(
-            env,
-            js_class_name.as_ptr(),
-            js_name.len(),
-            Some(ctor),
-            ptr::null_mut(),
-            raw_props.len(),
-            raw_props.as_ptr(),
-            &mut class_ptr,
-          ),
-          "Failed to register class `{}` generate by struct `{}`",
-          &js_name,
-          &rust_name
-        );
-
-        let mut ctor_ref = ptr::null_mut();
-        sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
-
-        REGISTERED_CLASSES.with(|registered_classes| {
-          let mut registered_class = registered_classes.borrow_mut();
-          registered_class.insert(js_name, ctor_ref);
-        });
-
-        check_status_or_throw!(
-          env,
-          sys::napi_set_named_property(env, exports, js_class_name.as_ptr(), class_ptr),
-          "Failed to register class `{}` generate by struct `{}`",
-          &js_name,
-          &rust_name
-        );
-      }
+  let to_register_exports = MODULE_REGISTER_CALLBACK.lock().unwrap().clone();
+  for (name, callback) in to_register_exports.into_iter() {
+    let js_name = CString::new(name).unwrap();
+    if let Err(e) = callback(env).and_then(|v| {
+      check_status!(
+        sys::napi_set_named_property(env, exports, js_name.as_ptr(), v),
+        "Failed to register export `{}`",
+        name,
+      )
+    }) {
+      JsError::from(e).throw_into(env)
     }
-  });
+  }
+
+  if let Err(e) = define_classes(env, exports) {
+    JsError::from(e).throw_into(env);
+  }
 
   #[cfg(feature = "compat-mode")]
-  MODULE_EXPORTS.with(|callbacks| {
-    for callback in callbacks.take().into_iter() {
+  {
+    let callbacks = MODULE_EXPORTS.lock().unwrap().clone();
+    for callback in callbacks.into_iter() {
       if let Err(e) = callback(env, exports) {
         JsError::from(e).throw_into(env);
       }
     }
-  });
+  }
 
   #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-  if let Err(e) = check_status!(
-    sys::napi_add_env_cleanup_hook(env, Some(crate::shutdown_tokio_rt), ptr::null_mut()),
-    "Failed to initialize module",
-  ) {
-    JsError::from(e).throw_into(env);
+  {
+    TOKIO_RT_ENV_COUNT.fetch_add(1, Ordering::SeqCst);
+    if let Err(e) = check_status!(
+      sys::napi_add_env_cleanup_hook(env, Some(cleanup_tokio_rt), ptr::null_mut()),
+      "Failed to initialize module",
+    ) {
+      JsError::from(e).throw_into(env);
+    }
   }
 
   exports
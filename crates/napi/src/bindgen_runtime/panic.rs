@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::cell::RefCell;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+use std::pin::Pin;
+use std::sync::Once;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+use std::task::{Context, Poll};
+
+use crate::{Error, Result, Status};
+
+thread_local! {
+  /// Location of the last panic on this thread, the panic payload doesn't carry it
+  static PANIC_LOCATION: RefCell<Option<String>> = Default::default();
+}
+
+/// Record the panic locations, the previous hook is still called so the panics are reported as usual.
+///
+/// The hook is installed once for the process, a hook set later replaces it,
+/// and the panics are reported without their locations then.
+fn install_panic_hook() {
+  static INSTALL: Once = Once::new();
+
+  INSTALL.call_once(|| {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+      PANIC_LOCATION.with(|location| {
+        *location.borrow_mut() = info.location().map(|l| l.to_string());
+      });
+      previous_hook(info);
+    }));
+  });
+}
+
+fn panic_to_error(payload: Box<dyn Any + Send>) -> Error {
+  let message = match payload.downcast::<String>() {
+    Ok(message) => *message,
+    Err(payload) => match payload.downcast::<&'static str>() {
+      Ok(message) => (*message).to_owned(),
+      Err(_) => "Box<dyn Any>".to_owned(),
+    },
+  };
+
+  let reason = match PANIC_LOCATION.with(|location| location.borrow_mut().take()) {
+    Some(location) => format!("panicked at '{}', {}", message, location),
+    None => format!("panicked at '{}'", message),
+  };
+
+  Error::new(Status::GenericFailure, reason)
+}
+
+/// Run `f` and convert the panic in it into `Error`, with the panic message and location.
+///
+/// Unwinding across the FFI boundary is undefined behavior, so all the napi callbacks
+/// generated by `#[napi]` are run in it, unless `#[napi(no_catch_unwind)]` is set.
+pub fn catch_panic<R>(f: impl FnOnce() -> Result<R>) -> Result<R> {
+  install_panic_hook();
+  // `resume_unwind` doesn't call the hook, the location of an earlier panic mustn't be reported
+  PANIC_LOCATION.with(|location| location.borrow_mut().take());
+  panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(panic_to_error(payload)))
+}
+
+/// Resolve the panic in `fut` into `Error`, otherwise the promise of it will never be settled
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub(crate) struct CatchPanic<F>(pub(crate) Pin<Box<F>>);
+
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+impl<T, F: Future<Output = Result<T>>> Future for CatchPanic<F> {
+  type Output = Result<T>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let fut = self.0.as_mut();
+    catch_panic(move || Ok(fut.poll(cx))).unwrap_or_else(|e| Poll::Ready(Err(e)))
+  }
+}
@@ -16,6 +16,8 @@ use crate::{
 
 #[cfg(feature = "napi8")]
 use crate::async_cleanup_hook::AsyncCleanupHook;
+#[cfg(feature = "napi6")]
+use crate::bindgen_runtime;
 #[cfg(feature = "napi3")]
 use crate::cleanup_env::{CleanupEnvHook, CleanupEnvHookData};
 #[cfg(all(feature = "serde-json"))]
@@ -751,15 +753,15 @@ impl Env {
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
         let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
-        (*tagged_object).object.as_mut().ok_or(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, nothing attach to js_object".to_owned(),
-        })
+        (*tagged_object).object.as_mut().ok_or(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, nothing attach to js_object".to_owned(),
+        ))
       } else {
-        Err(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
-        })
+        Err(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
+        ))
       }
     }
   }
@@ -776,15 +778,15 @@ impl Env {
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
         let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
-        (*tagged_object).object.as_mut().ok_or(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, nothing attach to js_object".to_owned(),
-        })
+        (*tagged_object).object.as_mut().ok_or(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, nothing attach to js_object".to_owned(),
+        ))
       } else {
-        Err(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
-        })
+        Err(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
+        ))
       }
     }
   }
@@ -802,10 +804,10 @@ impl Env {
         Box::from_raw(unknown_tagged_object as *mut TaggedObject<T>);
         Ok(())
       } else {
-        Err(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
-        })
+        Err(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
+        ))
       }
     }
   }
@@ -900,15 +902,15 @@ impl Env {
       let type_id = unknown_tagged_object as *const TypeId;
       if *type_id == TypeId::of::<T>() {
         let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
-        (*tagged_object).object.as_mut().ok_or(Error {
-          status: Status::InvalidArg,
-          reason: "nothing attach to js_external".to_owned(),
-        })
+        (*tagged_object).object.as_mut().ok_or(Error::new(
+          Status::InvalidArg,
+          "nothing attach to js_external".to_owned(),
+        ))
       } else {
-        Err(Error {
-          status: Status::InvalidArg,
-          reason: "T on get_value_external is not the type of wrapped object".to_owned(),
-        })
+        Err(Error::new(
+          Status::InvalidArg,
+          "T on get_value_external is not the type of wrapped object".to_owned(),
+        ))
       }
     }
   }
@@ -1060,22 +1062,25 @@ impl Env {
     Hint: 'static,
     F: FnOnce(FinalizeContext<T, Hint>),
   {
-    check_status!(unsafe {
-      sys::napi_set_instance_data(
-        self.0,
-        Box::leak(Box::new((TaggedObject::new(native), finalize_cb))) as *mut (TaggedObject<T>, F)
-          as *mut c_void,
-        Some(
-          set_instance_finalize_callback::<T, Hint, F>
-            as unsafe extern "C" fn(
-              env: sys::napi_env,
-              finalize_data: *mut c_void,
-              finalize_hint: *mut c_void,
-            ),
-        ),
-        Box::leak(Box::new(hint)) as *mut Hint as *mut c_void,
-      )
-    })
+    // the instance data slot of the env is owned by the bindgen runtime,
+    // the data is stored in it and finalized along with the runtime data
+    unsafe {
+      bindgen_runtime::with_env_data(self.0, |env_data| {
+        env_data.instance_data = Some(bindgen_runtime::InstanceData {
+          data: Box::leak(Box::new((TaggedObject::new(native), finalize_cb)))
+            as *mut (TaggedObject<T>, F) as *mut c_void,
+          finalize_cb: Some(
+            set_instance_finalize_callback::<T, Hint, F>
+              as unsafe extern "C" fn(
+                env: sys::napi_env,
+                finalize_data: *mut c_void,
+                finalize_hint: *mut c_void,
+              ),
+          ),
+          finalize_hint: Box::leak(Box::new(hint)) as *mut Hint as *mut c_void,
+        });
+      })
+    }
   }
 
   /// This API retrieves data that was previously associated with the currently running Agent via `Env::set_instance_data()`.
@@ -1086,27 +1091,29 @@ impl Env {
   where
     T: 'static,
   {
-    let mut unknown_tagged_object: *mut c_void = ptr::null_mut();
     unsafe {
-      check_status!(sys::napi_get_instance_data(
-        self.0,
-        &mut unknown_tagged_object
-      ))?;
+      let unknown_tagged_object = bindgen_runtime::with_env_data(self.0, |env_data| {
+        env_data
+          .instance_data
+          .as_ref()
+          .map(|instance_data| instance_data.data)
+          .unwrap_or_else(ptr::null_mut)
+      })?;
       let type_id = unknown_tagged_object as *const TypeId;
       if unknown_tagged_object.is_null() {
         return Ok(None);
       }
       if *type_id == TypeId::of::<T>() {
         let tagged_object = unknown_tagged_object as *mut TaggedObject<T>;
-        (*tagged_object).object.as_mut().map(Some).ok_or(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, nothing attach to js_object".to_owned(),
-        })
+        (*tagged_object).object.as_mut().map(Some).ok_or(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, nothing attach to js_object".to_owned(),
+        ))
       } else {
-        Err(Error {
-          status: Status::InvalidArg,
-          reason: "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
-        })
+        Err(Error::new(
+          Status::InvalidArg,
+          "Invalid argument, T on unrwap is not the type of wrapped object".to_owned(),
+        ))
       }
     }
   }
@@ -4,28 +4,155 @@ use std::ffi::CString;
 use std::fmt;
 #[cfg(feature = "serde-json")]
 use std::fmt::Display;
+use std::mem;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
 
 #[cfg(feature = "serde-json")]
 use serde::{de, ser};
 #[cfg(feature = "serde-json")]
 use serde_json::Error as SerdeJSONError;
 
-use crate::{check_status, sys, Status};
+use crate::{
+  bindgen_prelude::{FromNapiValue, ToNapiValue},
+  bindgen_runtime, check_status, sys, Status,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Represent `JsError`.
 /// Return this Error in `js_function`, **napi-rs** will throw it as `JsError` for you.
-/// If you want throw it as `TypeError` or `RangeError`, you can use `JsTypeError/JsRangeError::from(Error).throw_into(env)`
-#[derive(Debug, Clone)]
+/// If you want throw it as `TypeError` or `RangeError`, use `Error::with_kind`
+/// or `JsTypeError/JsRangeError::from(Error).throw_into(env)`.
+///
+/// ```ignore
+/// Error::new(Status::InvalidArg, "Invalid port".to_owned())
+///   .with_code("ERR_INVALID_PORT")
+///   .with_kind(ErrorKind::RangeError)
+///   .with_property("port", 65536u32)
+/// ```
+#[derive(Clone)]
 pub struct Error {
   pub status: Status,
   pub reason: String,
+  /// `code` of the JavaScript error, the `status` is used if it's not set
+  code: Option<String>,
+  kind: ErrorKind,
+  cause: Option<Box<Error>>,
+  properties: Vec<(String, ErrorProperty)>,
+  /// The JavaScript exception this error is caught from, it's rethrown unchanged
+  exception: Option<Arc<JsException>>,
 }
 
-impl error::Error for Error {}
+type ErrorProperty = Arc<dyn Fn(sys::napi_env) -> Result<sys::napi_value> + Send + Sync>;
+
+/// Reference of a caught JavaScript exception
+struct JsException {
+  env: sys::napi_env,
+  reference: sys::napi_ref,
+  thread_id: ThreadId,
+  dropped_exceptions: DroppedExceptions,
+}
+
+// The reference is only accessed on the JavaScript thread of `env`,
+// it's queued into `dropped_exceptions` if the `Error` is dropped on other threads.
+unsafe impl Send for JsException {}
+unsafe impl Sync for JsException {}
+
+impl Drop for JsException {
+  fn drop(&mut self) {
+    if thread::current().id() == self.thread_id {
+      let status = unsafe { sys::napi_delete_reference(self.env, self.reference) };
+      debug_assert!(
+        status == sys::Status::napi_ok,
+        "Delete exception reference failed"
+      );
+    } else {
+      self.dropped_exceptions.push(self.reference);
+    }
+  }
+}
+
+/// References of the exceptions dropped out of the JavaScript thread of an env,
+/// they are deleted the next time the env is used on its JavaScript thread.
+#[derive(Clone, Default)]
+pub(crate) struct DroppedExceptions(Arc<Mutex<Vec<usize>>>);
+
+impl DroppedExceptions {
+  fn push(&self, reference: sys::napi_ref) {
+    if let Ok(mut references) = self.0.lock() {
+      references.push(reference as usize);
+    }
+  }
+
+  /// # Safety
+  ///
+  /// `env` must be the env of the references, on its JavaScript thread
+  pub(crate) unsafe fn release(&self, env: sys::napi_env) {
+    let references = match self.0.lock() {
+      Ok(mut references) => mem::take(&mut *references),
+      Err(_) => return,
+    };
+    for reference in references {
+      let status = sys::napi_delete_reference(env, reference as sys::napi_ref);
+      debug_assert!(
+        status == sys::Status::napi_ok,
+        "Delete exception reference failed"
+      );
+    }
+  }
+}
+
+/// Delete the references of the exceptions dropped on other threads
+///
+/// # Safety
+///
+/// `env` must be a valid env of the current thread
+pub(crate) unsafe fn release_dropped_exceptions(env: sys::napi_env) {
+  if let Ok(dropped_exceptions) =
+    bindgen_runtime::with_env_data(env, |env_data| env_data.dropped_exceptions.clone())
+  {
+    dropped_exceptions.release(env);
+  }
+}
+
+/// Class of the JavaScript error created from `Error`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+  Error,
+  TypeError,
+  RangeError,
+  /// Error class generated by `#[napi(error)]`, with its JavaScript name
+  Custom(&'static str),
+}
+
+impl fmt::Debug for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Error")
+      .field("status", &self.status)
+      .field("reason", &self.reason)
+      .field("code", &self.code)
+      .field("kind", &self.kind)
+      .field("cause", &self.cause)
+      .field(
+        "properties",
+        &self
+          .properties
+          .iter()
+          .map(|(key, _)| key)
+          .collect::<Vec<_>>(),
+      )
+      .finish()
+  }
+}
+
+impl error::Error for Error {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    self.cause.as_deref().map(|cause| cause as _)
+  }
+}
 
 #[cfg(feature = "serde-json")]
 impl ser::Error for Error {
@@ -60,39 +187,246 @@ impl fmt::Display for Error {
 
 impl Error {
   pub fn new(status: Status, reason: String) -> Self {
-    Error { status, reason }
-  }
-
-  pub fn from_status(status: Status) -> Self {
     Error {
       status,
-      reason: "".to_owned(),
+      reason,
+      code: None,
+      kind: ErrorKind::Error,
+      cause: None,
+      properties: vec![],
+      exception: None,
+    }
+  }
+
+  /// Take the pending JavaScript exception of `env`, it's rethrown unchanged
+  /// when the error is converted back to JavaScript.
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid env of the current thread
+  pub unsafe fn from_pending_exception(env: sys::napi_env) -> Self {
+    let mut exception = ptr::null_mut();
+    let status = sys::napi_get_and_clear_last_exception(env, &mut exception);
+    if status != sys::Status::napi_ok || exception.is_null() {
+      return Error::new(
+        Status::from(status),
+        "Failed to get the pending exception".to_owned(),
+      );
+    }
+
+    Error::from_js_exception(env, exception)
+  }
+
+  /// Hold the thrown (or rejected) JavaScript value `exception`, it's rethrown unchanged
+  /// when the error is converted back to JavaScript.
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid env of the current thread, and `exception` must be a value of it
+  pub unsafe fn from_js_exception(env: sys::napi_env, exception: sys::napi_value) -> Self {
+    let reason = exception_message(env, exception);
+    let dropped_exceptions =
+      match bindgen_runtime::with_env_data(env, |env_data| env_data.dropped_exceptions.clone()) {
+        Ok(dropped_exceptions) => dropped_exceptions,
+        Err(_) => return Error::new(Status::GenericFailure, reason),
+      };
+    dropped_exceptions.release(env);
+    // references to primitive values are not supported by older napi versions,
+    // the exception is held by an array instead
+    let mut holder = ptr::null_mut();
+    let mut reference = ptr::null_mut();
+    if sys::napi_create_array_with_length(env, 1, &mut holder) != sys::Status::napi_ok
+      || sys::napi_set_element(env, holder, 0, exception) != sys::Status::napi_ok
+      || sys::napi_create_reference(env, holder, 1, &mut reference) != sys::Status::napi_ok
+    {
+      return Error::new(Status::GenericFailure, reason);
     }
+
+    let mut error = Error::new(Status::PendingException, reason);
+    error.exception = Some(Arc::new(JsException {
+      env,
+      reference,
+      thread_id: thread::current().id(),
+      dropped_exceptions,
+    }));
+
+    error
+  }
+
+  /// Whether the error is caught from a JavaScript exception
+  pub fn is_js_exception(&self) -> bool {
+    self.exception.is_some()
+  }
+
+  pub fn from_status(status: Status) -> Self {
+    Error::new(status, "".to_owned())
   }
 
   pub fn from_reason(reason: String) -> Self {
-    Error {
-      status: Status::GenericFailure,
-      reason,
+    Error::new(Status::GenericFailure, reason)
+  }
+
+  /// Convert any Rust error, the chain of `source()` becomes the chain of `cause`
+  pub fn from_error(err: &(dyn error::Error + 'static)) -> Self {
+    let mut error = Error::from_reason(err.to_string());
+    if let Some(source) = err.source() {
+      error.cause = Some(Box::new(Error::from_error(source)));
+    }
+
+    error
+  }
+
+  /// Set the `code` of the JavaScript error
+  pub fn with_code<C: Into<String>>(mut self, code: C) -> Self {
+    self.code = Some(code.into());
+    self
+  }
+
+  /// Set the class of the JavaScript error
+  pub fn with_kind<K: Into<ErrorKind>>(mut self, kind: K) -> Self {
+    self.kind = kind.into();
+    self
+  }
+
+  /// Set the `cause` of the JavaScript error
+  pub fn with_cause<E: Into<Error>>(mut self, cause: E) -> Self {
+    self.cause = Some(Box::new(cause.into()));
+    self
+  }
+
+  /// Set an extra property on the JavaScript error
+  pub fn with_property<K, V>(mut self, key: K, value: V) -> Self
+  where
+    K: Into<String>,
+    V: ToNapiValue + Clone + Send + Sync + 'static,
+  {
+    self.properties.push((
+      key.into(),
+      Arc::new(move |env| unsafe { V::to_napi_value(env, value.clone()) }),
+    ));
+    self
+  }
+
+  /// `code` of the JavaScript error
+  pub fn code(&self) -> String {
+    match &self.code {
+      Some(code) => code.clone(),
+      None => format!("{:?}", self.status),
+    }
+  }
+
+  pub fn kind(&self) -> ErrorKind {
+    self.kind
+  }
+
+  pub fn cause(&self) -> Option<&Error> {
+    self.cause.as_deref()
+  }
+
+  /// Create the JavaScript error
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid env of the current thread
+  pub unsafe fn into_js_error(self, env: sys::napi_env) -> Result<sys::napi_value> {
+    release_dropped_exceptions(env);
+    if let Some(exception) = &self.exception {
+      if exception.env == env && exception.thread_id == thread::current().id() {
+        let mut holder = ptr::null_mut();
+        check_status!(
+          sys::napi_get_reference_value(env, exception.reference, &mut holder),
+          "Failed to get the caught exception"
+        )?;
+        let mut js_error = ptr::null_mut();
+        check_status!(
+          sys::napi_get_element(env, holder, 0, &mut js_error),
+          "Failed to get the caught exception"
+        )?;
+        return Ok(js_error);
+      }
+    }
+
+    let code = String::to_napi_value(env, self.code())?;
+    let message = String::to_napi_value(env, self.reason)?;
+    let mut js_error = ptr::null_mut();
+
+    match self.kind {
+      ErrorKind::Error => check_status!(
+        sys::napi_create_error(env, code, message, &mut js_error),
+        "Failed to create Error"
+      )?,
+      ErrorKind::TypeError => check_status!(
+        sys::napi_create_type_error(env, code, message, &mut js_error),
+        "Failed to create TypeError"
+      )?,
+      ErrorKind::RangeError => check_status!(
+        sys::napi_create_range_error(env, code, message, &mut js_error),
+        "Failed to create RangeError"
+      )?,
+      ErrorKind::Custom(js_name) => {
+        js_error = bindgen_runtime::new_error_class_instance(env, js_name, message)?;
+        set_error_property(env, js_error, "code", code)?;
+      }
+    }
+
+    if let Some(cause) = self.cause {
+      let cause = cause.into_js_error(env)?;
+      set_error_property(env, js_error, "cause", cause)?;
+    }
+
+    for (key, value) in self.properties {
+      set_error_property(env, js_error, &key, value(env)?)?;
     }
+
+    Ok(js_error)
   }
 }
 
+/// `exception.message` if it's an `Error`, otherwise `String(exception)`
+unsafe fn exception_message(env: sys::napi_env, exception: sys::napi_value) -> String {
+  let mut is_error = false;
+  sys::napi_is_error(env, exception, &mut is_error);
+
+  let mut message = exception;
+  if is_error {
+    let name = CString::new("message").unwrap();
+    sys::napi_get_named_property(env, exception, name.as_ptr(), &mut message);
+  }
+
+  let mut message_string = ptr::null_mut();
+  if sys::napi_coerce_to_string(env, message, &mut message_string) != sys::Status::napi_ok {
+    // `String(exception)` throws for `Symbol` and objects with throwing `toString`
+    let mut ignored = ptr::null_mut();
+    sys::napi_get_and_clear_last_exception(env, &mut ignored);
+    return "JavaScript exception".to_owned();
+  }
+
+  String::from_napi_value(env, message_string).unwrap_or_default()
+}
+
+unsafe fn set_error_property(
+  env: sys::napi_env,
+  js_error: sys::napi_value,
+  key: &str,
+  value: sys::napi_value,
+) -> Result<()> {
+  let name = CString::new(key)?;
+  check_status!(
+    sys::napi_set_named_property(env, js_error, name.as_ptr(), value),
+    "Failed to set `{}` of error",
+    key,
+  )
+}
+
 impl From<std::ffi::NulError> for Error {
   fn from(error: std::ffi::NulError) -> Self {
-    Error {
-      status: Status::GenericFailure,
-      reason: format!("{}", error),
-    }
+    Error::from_error(&error)
   }
 }
 
 impl From<std::io::Error> for Error {
   fn from(error: std::io::Error) -> Self {
-    Error {
-      status: Status::GenericFailure,
-      reason: format!("{}", error),
-    }
+    Error::from_error(&error)
   }
 }
 
@@ -134,33 +468,23 @@ macro_rules! impl_object_methods {
       ///
       /// This function is safety if env is not null ptr.
       pub unsafe fn into_value(self, env: sys::napi_env) -> sys::napi_value {
-        let error_status = format!("{:?}", self.0.status);
-        let status_len = error_status.len();
-        let error_code_string = CString::new(error_status).unwrap();
-        let reason_len = self.0.reason.len();
-        let reason = CString::new(self.0.reason).unwrap();
-        let mut error_code = ptr::null_mut();
-        let mut reason_string = ptr::null_mut();
-        let mut js_error = ptr::null_mut();
-        let create_code_status = sys::napi_create_string_utf8(
-          env,
-          error_code_string.as_ptr(),
-          status_len,
-          &mut error_code,
+        let js_error = self.into_error().into_js_error(env);
+        debug_assert!(
+          js_error.is_ok(),
+          "Create JavaScript error failed: {:?}",
+          js_error
         );
-        debug_assert!(create_code_status == sys::Status::napi_ok);
-        let create_reason_status =
-          sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string);
-        debug_assert!(create_reason_status == sys::Status::napi_ok);
-        let create_error_status = $kind(env, error_code, reason_string, &mut js_error);
-        debug_assert!(create_error_status == sys::Status::napi_ok);
-        js_error
+        js_error.unwrap_or(ptr::null_mut())
       }
 
       /// # Safety
       ///
       /// This function is safety if env is not null ptr.
       pub unsafe fn throw_into(self, env: sys::napi_env) {
+        // the error is caused by a JavaScript exception, which is still pending
+        if self.0.status == Status::PendingException && !self.0.is_js_exception() {
+          return;
+        }
         #[cfg(debug_assertions)]
         let reason = self.0.reason.clone();
         #[cfg(debug_assertions)]
@@ -168,6 +492,7 @@ macro_rules! impl_object_methods {
         let js_error = self.into_value(env);
         #[cfg(debug_assertions)]
         let throw_status = sys::napi_throw(env, js_error);
+        #[cfg(not(debug_assertions))]
         sys::napi_throw(env, js_error);
         #[cfg(debug_assertions)]
         assert!(
@@ -180,23 +505,16 @@ macro_rules! impl_object_methods {
       }
 
       pub fn throw(&self, env: sys::napi_env) -> Result<()> {
-        let error_status = format!("{:?}", self.0.status);
-        let status_len = error_status.len();
-        let error_code_string = CString::new(error_status).unwrap();
-        let reason_len = self.0.reason.len();
-        let reason = CString::new(self.0.reason.clone()).unwrap();
-        let mut error_code = ptr::null_mut();
-        let mut reason_string = ptr::null_mut();
-        let mut js_error = ptr::null_mut();
-        check_status!(unsafe {
-          sys::napi_create_string_utf8(env, error_code_string.as_ptr(), status_len, &mut error_code)
-        })?;
-        check_status!(unsafe {
-          sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
-        })?;
-        check_status!(unsafe { $kind(env, error_code, reason_string, &mut js_error) })?;
+        let js_error = unsafe { Self(self.0.clone()).into_error().into_js_error(env)? };
         check_status!(unsafe { sys::napi_throw(env, js_error) })
       }
+
+      fn into_error(self) -> Error {
+        match $kind {
+          Some(kind) => self.0.with_kind(kind),
+          None => self.0,
+        }
+      }
     }
 
     impl From<Error> for $js_value {
@@ -207,9 +525,9 @@ macro_rules! impl_object_methods {
   };
 }
 
-impl_object_methods!(JsError, sys::napi_create_error);
-impl_object_methods!(JsTypeError, sys::napi_create_type_error);
-impl_object_methods!(JsRangeError, sys::napi_create_range_error);
+impl_object_methods!(JsError, None::<ErrorKind>);
+impl_object_methods!(JsTypeError, Some(ErrorKind::TypeError));
+impl_object_methods!(JsRangeError, Some(ErrorKind::RangeError));
 
 #[doc(hidden)]
 #[macro_export]
@@ -219,6 +537,24 @@ macro_rules! error {
   };
 }
 
+/// `check_status!`, but the pending JavaScript exception is taken into the `Error`,
+/// so it could be rethrown unchanged.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! check_pending_exception {
+  ($env:expr, $code:expr, $($msg:tt)*) => {{
+    let env = $env;
+    match $code {
+      $crate::sys::Status::napi_pending_exception => {
+        #[allow(unused_unsafe)]
+        let error = unsafe { $crate::Error::from_pending_exception(env) };
+        Err(error)
+      }
+      c => $crate::check_status!(c, $($msg)*),
+    }
+  }};
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! check_status {
@@ -2,7 +2,7 @@ use std::ptr;
 
 use super::Value;
 use crate::bindgen_runtime::TypeName;
-use crate::{check_status, ValueType};
+use crate::{check_pending_exception, ValueType};
 use crate::{sys, Env, Error, JsObject, JsUnknown, NapiRaw, NapiValue, Result, Status};
 
 pub struct JsFunction(pub(crate) Value);
@@ -51,16 +51,20 @@ impl JsFunction {
       .map(|arg| unsafe { arg.raw() })
       .collect::<Vec<sys::napi_value>>();
     let mut return_value = ptr::null_mut();
-    check_status!(unsafe {
-      sys::napi_call_function(
-        self.0.env,
-        raw_this,
-        self.0.value,
-        args.len(),
-        raw_args.as_ptr(),
-        &mut return_value,
-      )
-    })?;
+    check_pending_exception!(
+      self.0.env,
+      unsafe {
+        sys::napi_call_function(
+          self.0.env,
+          raw_this,
+          self.0.value,
+          args.len(),
+          raw_args.as_ptr(),
+          &mut return_value,
+        )
+      },
+      "Failed to call function"
+    )?;
 
     unsafe { JsUnknown::from_raw(self.0.env, return_value) }
   }
@@ -78,16 +82,20 @@ impl JsFunction {
       })
       .ok_or_else(|| Error::new(Status::GenericFailure, "Get raw this failed".to_owned()))?;
     let mut return_value = ptr::null_mut();
-    check_status!(unsafe {
-      sys::napi_call_function(
-        self.0.env,
-        raw_this,
-        self.0.value,
-        0,
-        ptr::null_mut(),
-        &mut return_value,
-      )
-    })?;
+    check_pending_exception!(
+      self.0.env,
+      unsafe {
+        sys::napi_call_function(
+          self.0.env,
+          raw_this,
+          self.0.value,
+          0,
+          ptr::null_mut(),
+          &mut return_value,
+        )
+      },
+      "Failed to call function"
+    )?;
 
     unsafe { JsUnknown::from_raw(self.0.env, return_value) }
   }
@@ -106,15 +114,19 @@ impl JsFunction {
       .iter()
       .map(|arg| unsafe { arg.raw() })
       .collect::<Vec<sys::napi_value>>();
-    check_status!(unsafe {
-      sys::napi_new_instance(
-        self.0.env,
-        self.0.value,
-        length,
-        raw_args.as_ptr(),
-        &mut js_instance,
-      )
-    })?;
+    check_pending_exception!(
+      self.0.env,
+      unsafe {
+        sys::napi_new_instance(
+          self.0.env,
+          self.0.value,
+          length,
+          raw_args.as_ptr(),
+          &mut js_instance,
+        )
+      },
+      "Failed to create instance"
+    )?;
     Ok(unsafe { JsObject::from_raw_unchecked(self.0.env, js_instance) })
   }
 }
@@ -88,6 +88,10 @@ impl Property {
     }
   }
 
+  pub(crate) fn is_static(&self) -> bool {
+    self.attrs == PropertyAttributes::Static
+  }
+
   pub fn with_ctor(mut self, callback: Callback) -> Self {
     self.method = Some(callback);
     self.is_ctor = true;
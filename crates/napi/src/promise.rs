@@ -3,7 +3,10 @@ use std::marker::PhantomData;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
 
-use crate::{check_status, sys, JsError, Result};
+use crate::{
+  bindgen_runtime::catch_panic, check_status, error::release_dropped_exceptions, sys, JsError,
+  Result,
+};
 
 pub struct FuturePromise<Data, Resolver: FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>> {
   deferred: sys::napi_deferred,
@@ -110,7 +113,7 @@ unsafe extern "C" fn call_js_cb<
   let value = Box::from_raw(data as *mut Result<Data>);
   let resolver = future_promise.resolver;
   let deferred = future_promise.deferred;
-  let js_value_to_resolve = value.and_then(move |v| (resolver)(env, v));
+  let js_value_to_resolve = value.and_then(move |v| catch_panic(move || (resolver)(env, v)));
   match js_value_to_resolve {
     Ok(v) => {
       let status = sys::napi_resolve_deferred(env, deferred, v);
@@ -121,4 +124,6 @@ unsafe extern "C" fn call_js_cb<
       debug_assert!(status == sys::Status::napi_ok, "Reject promise failed");
     }
   };
+  // the errors handled in the future are likely dropped on the tokio threads
+  release_dropped_exceptions(env);
 }
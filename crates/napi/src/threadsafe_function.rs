@@ -8,7 +8,13 @@ use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
-use crate::{check_status, sys, Env, Error, JsError, JsFunction, NapiRaw, Result, Status};
+use crate::bindgen_runtime::{
+  catch_panic, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue,
+};
+use crate::{
+  check_status, sys, Env, Error, JsError, JsFunction, JsUnknown, NapiRaw, NapiValue, Result,
+  Status, ValueType,
+};
 
 use sys::napi_threadsafe_function_call_mode;
 
@@ -327,6 +333,38 @@ impl<T: 'static, ES: ErrorStrategy::T> Drop for ThreadsafeFunction<T, ES> {
   }
 }
 
+impl<T: ToNapiValue + 'static, ES: ErrorStrategy::T> TypeName for ThreadsafeFunction<T, ES> {
+  fn type_name() -> &'static str {
+    "ThreadsafeFunction"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Function
+  }
+}
+
+impl<T: ToNapiValue + 'static, ES: ErrorStrategy::T> FromNapiValue for ThreadsafeFunction<T, ES> {
+  /// Create a `ThreadsafeFunction` from the JavaScript function argument of `#[napi]` functions,
+  /// the value passed to `call` is converted with `ToNapiValue`.
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let func = JsFunction::from_raw(env, napi_val)?;
+
+    Self::create(env, &func, 0, |ctx: ThreadSafeCallContext<T>| {
+      let raw_env = ctx.env.raw();
+      let value = T::to_napi_value(raw_env, ctx.value)?;
+      Ok(vec![JsUnknown::from_raw_unchecked(raw_env, value)])
+    })
+  }
+}
+
+impl<T: ToNapiValue + 'static, ES: ErrorStrategy::T> ValidateNapiValue
+  for ThreadsafeFunction<T, ES>
+{
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Function]
+  }
+}
+
 unsafe extern "C" fn thread_finalize_cb<T: 'static, V: NapiRaw, R>(
   _raw_env: sys::napi_env,
   finalize_data: *mut c_void,
@@ -356,10 +394,13 @@ unsafe extern "C" fn call_js_cb<T: 'static, V: NapiRaw, R, ES>(
   let mut recv = ptr::null_mut();
   sys::napi_get_undefined(raw_env, &mut recv);
 
+  // panics can't unwind across the FFI boundary, they are passed to the callback as errors
   let ret = val.and_then(|v| {
-    (ctx)(ThreadSafeCallContext {
-      env: Env::from_raw(raw_env),
-      value: v,
+    catch_panic(|| {
+      (ctx)(ThreadSafeCallContext {
+        env: Env::from_raw(raw_env),
+        value: v,
+      })
     })
   });
 
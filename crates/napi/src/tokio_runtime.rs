@@ -1,42 +1,28 @@
-use std::{ffi::c_void, future::Future, ptr};
-
-use crate::{check_status, promise, sys, Result};
-use once_cell::sync::Lazy;
-use tokio::{runtime::Handle, sync::mpsc};
-
-static RT: Lazy<(Handle, mpsc::Sender<()>)> = Lazy::new(|| {
-  let runtime = tokio::runtime::Runtime::new();
-  let (sender, mut receiver) = mpsc::channel::<()>(1);
-  runtime
-    .map(|rt| {
-      let h = rt.handle();
-      let handle = h.clone();
-      handle.spawn(async move {
-        if receiver.recv().await.is_some() {
-          rt.shutdown_background();
-        }
-      });
-
-      (handle, sender)
-    })
-    .expect("Create tokio runtime failed")
-});
+use std::{ffi::c_void, future::Future, ptr, sync::Mutex};
+
+use crate::{bindgen_runtime::CatchPanic, check_status, promise, sys, Result};
+use tokio::runtime::Runtime;
+
+/// The runtime is shared by all envs, it's created on the first spawned future
+/// and could be created again after it's shut down, when the addon is loaded into a new env.
+static RT: Mutex<Option<Runtime>> = Mutex::new(None);
 
 #[doc(hidden)]
 #[inline(never)]
 pub extern "C" fn shutdown_tokio_rt(_arg: *mut c_void) {
-  let sender = &RT.1;
-  sender
-    .clone()
-    .try_send(())
-    .expect("Shutdown tokio runtime failed");
+  if let Some(rt) = RT.lock().unwrap().take() {
+    rt.shutdown_background();
+  }
 }
 
 pub fn spawn<F>(fut: F)
 where
   F: 'static + Send + Future<Output = ()>,
 {
-  RT.0.spawn(fut);
+  RT.lock()
+    .unwrap()
+    .get_or_insert_with(|| Runtime::new().expect("Create tokio runtime failed"))
+    .spawn(fut);
 }
 
 pub fn execute_tokio_future<
@@ -54,7 +40,8 @@ pub fn execute_tokio_future<
   check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) })?;
 
   let future_promise = promise::FuturePromise::new(env, deferred, resolver)?;
-  let future_to_resolve = promise::resolve_from_future(future_promise.start()?, fut);
+  let future_to_resolve =
+    promise::resolve_from_future(future_promise.start()?, CatchPanic(Box::pin(fut)));
   spawn(future_to_resolve);
 
   Ok(promise)
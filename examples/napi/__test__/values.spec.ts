@@ -1,10 +1,35 @@
 import { join } from 'path'
+import { Worker } from 'worker_threads'
 
 import test from 'ava'
 
 import {
   add,
+  dateToNumber,
+  addOneSecond,
+  addOneDay,
+  toIsoString,
+  createDate,
+  openDb,
+  dbRecordCount,
+  createCache,
+  cacheCapacity,
+  createExternalNumber,
+  externalNumberPlusOne,
+  wrappingAddU8,
+  sumSmall,
+  toF32,
+  checkedIndex,
+  usizeMax,
+  strictU8,
+  bigintAdd,
+  createBigInt,
+  u64MaxPlus,
+  i128Negate,
   fibonacci,
+  pow,
+  sumAll,
+  joinWords,
   contains,
   concatLatin1,
   concatStr,
@@ -13,20 +38,63 @@ import {
   getWords,
   sumNums,
   getCwd,
+  callWithOne,
+  callFunction,
+  callWithThis,
+  createInstance,
+  Emitter,
   Animal,
+  Dog,
+  getAnimalName,
   Kind,
   ClassWithFactory,
+  Counter,
+  Base,
   CustomNumEnum,
   enumToI32,
+  oppositeDirection,
+  shapeArea,
+  scaleShape,
   listObjKeys,
   createObj,
+  greetThis,
+  normalizeConfig,
+  configTimeout,
   mapOption,
   readFile,
+  callThreadsafeFunction,
+  threadsafeFunctionThrowError,
+  threadsafeFunctionFatalMode,
   throwError,
+  validatePort,
+  readConfig,
+  panicWithMessage,
+  panicInAsync,
+  resumePanic,
+  Fragile,
+  addUnchecked,
+  ValidationError,
   readPackageJson,
   getPackageJsonName,
   getBuffer,
+  appendBuffer,
+  bufferPassThrough,
+  copyBuffer,
+  fillBuffer,
+  convertU32Array,
+  createExternalTypedArray,
+  mutateTypedArray,
+  sumFloat64Array,
+  doubleInt32Array,
+  hashBytes,
+  reverseBytes,
+  scaleFloat32Slice,
+  sumFloat32Slice,
   readFileAsync,
+  reverseBufferAsync,
+  asyncPlus100,
+  plus100OrZero,
+  delayWithSignal,
   eitherStringOrNumber,
   returnEither,
   either3,
@@ -47,6 +115,92 @@ test('number', (t) => {
   )
 })
 
+test('small numbers', (t) => {
+  t.is(wrappingAddU8(200, 100), 44)
+  // non-strict arguments are saturated
+  t.is(wrappingAddU8(-1, 0), 0)
+  t.is(wrappingAddU8(300, 0), 255)
+  t.is(sumSmall(-1, 2, -3), -2)
+  t.is(toF32(0.5), 0.5)
+  t.is(toF32(0.1), Math.fround(0.1))
+  t.is(checkedIndex(3, 2), 2)
+  t.is(checkedIndex(3, 3), null)
+  // and truncated
+  t.is(checkedIndex(3.9, 2), 2)
+  t.is(checkedIndex(-1, 0), null)
+  t.is(usizeMax(), 2 ** 64)
+  t.is(strictU8(255), 255)
+
+  t.throws(() => strictU8(256), {
+    instanceOf: RangeError,
+    message: 'Value 256 is out of range of rust type `u8`',
+  })
+  t.throws(() => strictU8(-1), {
+    instanceOf: RangeError,
+    message: 'Value -1 is out of range of rust type `u8`',
+  })
+  t.throws(() => strictU8(1.5), {
+    instanceOf: RangeError,
+    message: 'Expect value to be an integer of rust type `u8`, but received 1.5',
+  })
+  t.throws(() => checkedIndex(3, NaN), {
+    instanceOf: RangeError,
+    message: 'Expect value to be an integer of rust type `usize`, but received NaN',
+  })
+  t.throws(
+    // @ts-expect-error
+    () => strictU8('1'),
+    { message: 'Expect value to be Number, but received String' },
+  )
+})
+
+test('bigint', (t) => {
+  t.is(bigintAdd(BigInt(1), BigInt(2)), BigInt(3))
+  t.is(bigintAdd(BigInt(-5), BigInt(2)), BigInt(-3))
+  t.throws(() => bigintAdd(BigInt(2) ** BigInt(126), BigInt(2) ** BigInt(126)), {
+    instanceOf: RangeError,
+    message: 'BigInt is out of range',
+  })
+  t.is(createBigInt(), BigInt('-3689348814741910323300'))
+  t.is(u64MaxPlus(BigInt(1)), BigInt('18446744073709551616'))
+  t.is(i128Negate(BigInt('-170141183460469231731687303715884105727')), BigInt('170141183460469231731687303715884105727'))
+
+  t.throws(() => u64MaxPlus(BigInt(-1)), {
+    instanceOf: RangeError,
+    message: 'BigInt is out of range of rust type `u64`',
+  })
+  t.throws(() => u64MaxPlus(BigInt('18446744073709551616')), {
+    instanceOf: RangeError,
+  })
+  t.throws(() => i128Negate(BigInt(2) ** BigInt(127)), {
+    instanceOf: RangeError,
+    message: 'BigInt is out of range of rust type `i128`',
+  })
+})
+
+test('arguments', (t) => {
+  t.is(pow(3), 9)
+  t.is(pow(3, undefined), 9)
+  t.is(pow(3, 3), 27)
+  t.is(mapOption(), null)
+
+  t.throws(
+    // @ts-expect-error
+    () => add(1),
+    {
+      instanceOf: TypeError,
+      message: 'Expected at least 2 arguments, got 1',
+    },
+  )
+})
+
+test('rest arguments', (t) => {
+  t.is(sumAll(1), 1)
+  t.is(sumAll(1, 2, 3, 4), 10)
+  t.is(joinWords(', '), '')
+  t.is(joinWords(', ', 'foo', 'bar', 'baz'), 'foo, bar, baz')
+})
+
 test('string', (t) => {
   t.true(contains('hello', 'ell'))
   t.false(contains('John', 'jn'))
@@ -71,6 +225,44 @@ test('enum', (t) => {
   t.is(enumToI32(CustomNumEnum.Eight), 8)
 })
 
+test('string enum', (t) => {
+  t.is(oppositeDirection('Up'), 'Down')
+  t.is(oppositeDirection('left'), 'right')
+  t.throws(() => oppositeDirection('Left' as any), {
+    message: 'value `Left` does not match any variant of enum `Direction`',
+  })
+})
+
+test('data-carrying enum', (t) => {
+  t.is(shapeArea({ type: 'Rect', width: 2, height: 3 }), 6)
+  t.is(shapeArea({ type: 'Empty' }), 0)
+  t.deepEqual(scaleShape({ type: 'Circle', radius: 1.5 }, 2), {
+    type: 'Circle',
+    radius: 3,
+  })
+  // optional fields are omitted when they are absent or `undefined`
+  t.is(shapeArea({ type: 'Square', side: 2 }), 4)
+  t.deepEqual(scaleShape({ type: 'Square', side: 1, label: undefined }, 2), {
+    type: 'Square',
+    side: 2,
+  })
+  t.deepEqual(scaleShape({ type: 'Square', side: 1, label: 'foo' }, 2), {
+    type: 'Square',
+    side: 2,
+    label: 'foo',
+  })
+  t.deepEqual(scaleShape({ type: 'Labeled', field0: 'foo' }, 2), {
+    type: 'Labeled',
+    field0: 'foo',
+  })
+  t.throws(() => shapeArea({ type: 'Rect', width: 2 } as any), {
+    message: 'Missing field `height` of variant `Rect` in enum `Shape`',
+  })
+  t.throws(() => shapeArea({ type: 'Triangle' } as any), {
+    message: 'type `Triangle` does not match any variant of enum `Shape`',
+  })
+})
+
 test('class', (t) => {
   const dog = new Animal(Kind.Dog, '旺财')
 
@@ -82,6 +274,26 @@ test('class', (t) => {
   t.is(dog.name, '可乐')
 })
 
+test('class inheritance', (t) => {
+  const dog = new Dog('旺财')
+
+  t.true(dog instanceof Dog)
+  t.true(dog instanceof Animal)
+  t.is(Object.getPrototypeOf(Dog), Animal)
+  t.is(dog.kind, Kind.Dog)
+  t.is(dog.whoami(), 'Dog: 旺财')
+  t.is(Dog.getDogKind(), Kind.Dog)
+  t.is(getAnimalName(dog), '旺财')
+
+  t.is(dog.learnTrick(), 1)
+  t.is(dog.tricks, 1)
+
+  dog.rename('可乐')
+  t.is(dog.name, '可乐')
+  dog.name = '旺财'
+  t.is(getAnimalName(dog), '旺财')
+})
+
 test('class factory', (t) => {
   const duck = ClassWithFactory.withName('Default')
   t.is(duck.name, 'Default')
@@ -96,6 +308,82 @@ test('class factory', (t) => {
   t.is(doge.name, '旺财')
 })
 
+test('class static members', (t) => {
+  t.is(Animal.DEFAULT_NAME, 'Default')
+  t.is(Animal.withKind(Kind.Cat).name, Animal.DEFAULT_NAME)
+  t.is(Animal.kindCount, 3)
+  t.is(Animal.getDogKind(), Kind.Dog)
+  t.is(Object.getOwnPropertyDescriptor(Animal, 'DEFAULT_NAME')?.set, undefined)
+  t.is(Dog.DEFAULT_NAME, 'Default')
+})
+
+test('class instances are borrow checked', (t) => {
+  const counter = new Counter(1)
+
+  t.is(
+    counter.readWith(() => {
+      t.is(counter.count, 1)
+    }),
+    1,
+  )
+  t.throws(() => counter.increaseWith(() => counter.count), {
+    message: "Failed to borrow `Counter`, it's already mutably borrowed",
+  })
+  t.throws(() => counter.readWith(() => counter.increaseWith(() => {})), {
+    message: "Failed to borrow `Counter` mutably, it's already borrowed",
+  })
+  t.throws(
+    () =>
+      counter.increaseWith(() => {
+        counter.count = 10
+      }),
+    {
+      message: "Failed to borrow `Counter` mutably, it's already borrowed",
+    },
+  )
+  t.throws(() => counter.addFrom(counter), {
+    message: "Failed to borrow `Counter`, it's already mutably borrowed",
+  })
+
+  // borrows are released after the conflicts
+  t.is(counter.count, 3)
+  const other = new Counter(10)
+  t.is(counter.addFrom(other), 13)
+  t.is(
+    counter.increaseWith(() => other.increaseWith(() => {})),
+    14,
+  )
+  t.is(other.count, 11)
+})
+
+test('class instances are type checked', (t) => {
+  const counter = new Counter(1)
+
+  for (const value of [counter, {}, Object.create(Animal.prototype)]) {
+    t.throws(() => getAnimalName(value as unknown as Animal), {
+      instanceOf: TypeError,
+      message: 'Expect value to be an instance of class `Animal`',
+    })
+  }
+  t.throws(() => counter.addFrom(new Animal(Kind.Cat, 'Tom') as any), {
+    instanceOf: TypeError,
+    message: 'Expect value to be an instance of class `Counter`',
+  })
+  t.is(getAnimalName(new Dog('旺财')), '旺财')
+})
+
+test('class constructor receives new.target', (t) => {
+  const base = new Base()
+  t.is(base.constructedBy, 'Base')
+  t.false(base.isSubclass)
+
+  class Derived extends Base {}
+  const derived = new Derived()
+  t.true(derived instanceof Base)
+  t.is(derived.constructedBy, 'Derived')
+  t.true(derived.isSubclass)
+})
+
 test('callback', (t) => {
   getCwd((cwd) => {
     t.is(cwd, process.cwd())
@@ -114,11 +402,147 @@ test('callback', (t) => {
   })
 })
 
+test('typed function', (t) => {
+  t.is(
+    callFunction((a, b) => a + b),
+    3,
+  )
+  t.is(
+    callWithThis(
+      function (this: { name: string }) {
+        return this.name
+      },
+      { name: 'napi' },
+    ),
+    'napi',
+  )
+
+  function Named(this: { name: string }, name: string) {
+    this.name = name
+  }
+  const instance = createInstance(Named, 'napi')
+  t.true(instance instanceof Named)
+  t.is((instance as { name: string }).name, 'napi')
+
+  const error = new TypeError('thrown in function')
+  const thrown = t.throws(() =>
+    callFunction(() => {
+      throw error
+    }),
+  )
+  t.is(thrown, error)
+  t.throws(
+    // @ts-expect-error
+    () => callFunction(() => 'not a number'),
+    { message: 'Failed to convert napi value into rust type `u32`' },
+  )
+})
+
+test('function reference', (t) => {
+  const emitter = new Emitter((value) => value * 2)
+  t.is(emitter.emit(1), 2)
+  t.is(emitter.emit(2), 4)
+  t.is(emitter.emitted, 2)
+})
+
+test('exceptions thrown in callback are rethrown unchanged', (t) => {
+  t.is(
+    callWithOne((n) => n + 1),
+    3,
+  )
+
+  const error = new TypeError('thrown in callback')
+  const thrown = t.throws(() =>
+    callWithOne(() => {
+      throw error
+    }),
+  )
+  t.is(thrown, error)
+
+  try {
+    callWithOne(() => {
+      throw 'not an error'
+    })
+    t.fail('should throw')
+  } catch (e) {
+    t.is(e, 'not an error')
+  }
+})
+
+test('threadsafe function', async (t) => {
+  const values = await new Promise<number[]>((resolve, reject) => {
+    const received: number[] = []
+    callThreadsafeFunction((err, value) => {
+      if (err) {
+        return reject(err)
+      }
+      received.push(value)
+      if (received.length === 100) {
+        resolve(received)
+      }
+    })
+  })
+  t.deepEqual(
+    values.sort((a, b) => a - b),
+    Array.from({ length: 100 }, (_, i) => i),
+  )
+
+  t.throws(
+    // @ts-expect-error
+    () => callThreadsafeFunction(1),
+    null,
+    'expect Function, got: Number',
+  )
+})
+
+test('threadsafe function receives error', async (t) => {
+  const err = await new Promise<Error | null>((resolve) => {
+    threadsafeFunctionThrowError((err) => resolve(err))
+  })
+  t.is(err?.message, 'ThrowFromNative')
+})
+
+test('threadsafe function in fatal mode', async (t) => {
+  const args = await new Promise<unknown[]>((resolve) => {
+    threadsafeFunctionFatalMode((...args) => resolve(args))
+  })
+  t.deepEqual(args, [true])
+})
+
 test('object', (t) => {
   t.deepEqual(listObjKeys({ name: 'John Doe', age: 20 }), ['name', 'age'])
   t.deepEqual(createObj(), { test: 1 })
 })
 
+test('function receives this', (t) => {
+  t.is(greetThis.call({ name: 'napi' }, 'Hello'), 'Hello, napi')
+
+  const obj = { name: 'method', greet: greetThis }
+  t.is(obj.greet('Hi'), 'Hi, method')
+})
+
+test('object with optional fields', (t) => {
+  t.deepEqual(normalizeConfig({ name: 'napi' }), {
+    name: 'napi',
+    retries: 3,
+    logLevel: 'info',
+  })
+  t.deepEqual(
+    normalizeConfig({ name: 'napi', retries: 5, logLevel: 'debug', timeout: 10 }),
+    { name: 'napi', retries: 5, logLevel: 'debug', timeout: 10 },
+  )
+  t.is(configTimeout({ name: 'napi', timeout: undefined }), 1000)
+  t.is(configTimeout({ name: 'napi', timeout: 5 }), 5)
+  t.throws(
+    // @ts-expect-error
+    () => normalizeConfig({ retries: 1 }),
+    {
+      code: 'InvalidArg',
+      message: 'Missing field `name` in struct `Config`',
+    },
+  )
+})
+
 test('Option', (t) => {
   t.is(mapOption(null), null)
   t.is(mapOption(3), 4)
@@ -128,11 +552,81 @@ test('Result', (t) => {
   t.throws(() => throwError(), null, 'Manual Error')
 })
 
+test('structured errors', (t) => {
+  t.is(validatePort(8080), 8080)
+
+  t.throws(() => validatePort(70000), {
+    instanceOf: RangeError,
+    code: 'ERR_OUT_OF_RANGE',
+    message: 'Port 70000 is out of range',
+  })
+
+  const validationError = t.throws(() => validatePort(80), {
+    instanceOf: ValidationError,
+    code: 'ERR_RESERVED_PORT',
+    message: 'Port 80 is reserved',
+  })
+  t.true(validationError instanceof Error)
+  t.is(validationError.name, 'ValidationError')
+  t.is((validationError as any).port, 80)
+  t.true(validationError.stack!.includes('values.spec'))
+  const manual = new ValidationError('manual')
+  t.true(manual instanceof Error)
+  t.is(manual.message, 'manual')
+
+  const configError = t.throws(() => readConfig('/path/not/exist'), {
+    code: 'ERR_CONFIG',
+    message: 'Failed to read config `/path/not/exist`',
+  })
+  t.true((configError as any).cause instanceof Error)
+})
+
+test('panics are thrown as errors', async (t) => {
+  const error = t.throws(() => panicWithMessage('boom'), {
+    instanceOf: Error,
+  })
+  t.true(error.message.startsWith("panicked at 'boom', "))
+  t.true(error.message.includes('error.rs'))
+
+  const asyncError = await t.throwsAsync(() => panicInAsync('async boom'))
+  t.true(asyncError.message.startsWith("panicked at 'async boom', "))
+
+  t.is(addUnchecked(1, 2), 3)
+})
+
+test('panics re-raised without hook have no location', (t) => {
+  t.throws(() => panicWithMessage('boom'))
+  t.throws(() => resumePanic('resumed'), {
+    message: "panicked at 'resumed'",
+  })
+})
+
+test('panics in class constructor and accessors', (t) => {
+  t.throws(() => new Fragile(0), {
+    message: /^panicked at 'value of Fragile must not be 0', /,
+  })
+
+  const fragile = new Fragile(13)
+  t.throws(() => fragile.value, {
+    message: /^panicked at 'value of Fragile is 13', /,
+  })
+  t.throws(
+    () => {
+      fragile.value = 0
+    },
+    {
+      message: /^panicked at 'value of Fragile must not be 0', /,
+    },
+  )
+  fragile.value = 1
+  t.is(fragile.value, 1)
+})
+
 test('serde-json', (t) => {
   const packageJson = readPackageJson()
   t.is(packageJson.name, 'napi-rs')
   t.is(packageJson.version, '0.0.0')
-  t.is(packageJson.dependencies, null)
+  t.is(packageJson.dependencies, undefined)
   t.snapshot(Object.keys(packageJson.devDependencies!).sort())
 
   t.is(getPackageJsonName(packageJson), 'napi-rs')
@@ -142,6 +636,77 @@ test('buffer', (t) => {
   t.is(getBuffer().toString('utf-8'), 'Hello world')
 })
 
+test('buffer round trip', (t) => {
+  const buf = Buffer.from('Hello')
+  t.is(bufferPassThrough(buf), buf)
+  const copied = copyBuffer(buf)
+  t.not(copied, buf)
+  t.is(fillBuffer(buf, 1), buf)
+  t.deepEqual(buf, Buffer.from([1, 1, 1, 1, 1]))
+  t.is(copied.toString('utf-8'), 'Hello')
+
+  const appended = appendBuffer(Buffer.from('Hello'))
+  t.not(appended, buf)
+  t.is(appended.toString('utf-8'), 'Hello!')
+})
+
+const GcTest =
+  typeof global.gc === 'function' && typeof WeakRef !== 'undefined'
+    ? test
+    : test.skip
+
+GcTest('buffer received from JavaScript is collectable', async (t) => {
+  let buf: Buffer | undefined = Buffer.alloc(1024 * 1024)
+  const weakRef = new WeakRef(buf)
+  bufferPassThrough(buf)
+  buf = undefined
+  await new Promise((resolve) => setTimeout(resolve, 0))
+  global.gc!()
+  t.is(weakRef.deref(), undefined)
+})
+
+test('TypedArray', (t) => {
+  t.deepEqual(
+    convertU32Array(new Uint32Array([1, 2, 3, 4, 5])),
+    [1, 2, 3, 4, 5],
+  )
+  t.deepEqual(createExternalTypedArray(), new Uint32Array([1, 2, 3, 4, 5]))
+  const mutable = new Float32Array([1, 2, 3, 4, 5])
+  mutateTypedArray(mutable)
+  t.deepEqual(mutable, new Float32Array([2.0, 4.0, 6.0, 8.0, 10.0]))
+  t.is(sumFloat64Array(new Float64Array([1.5, 2.5, 3])), 7)
+  t.deepEqual(
+    doubleInt32Array(new Int32Array([1, -2])),
+    new Int32Array([2, -4]),
+  )
+
+  t.throws(
+    // @ts-expect-error
+    () => sumFloat64Array(new Float32Array([1, 2])),
+    null,
+    'Expect value to be Float64Array, but received Float32Array',
+  )
+})
+
+test('borrowed slice', (t) => {
+  t.is(hashBytes(Buffer.from('hello')), 1335831723)
+  t.is(hashBytes(new Uint8Array(Buffer.from('hello'))), 1335831723)
+  const buf = Buffer.from('abc')
+  reverseBytes(buf)
+  t.is(buf.toString(), 'cba')
+  const floats = new Float32Array([1, 2, 3])
+  scaleFloat32Slice(floats, 2)
+  t.deepEqual(floats, new Float32Array([2, 4, 6]))
+  t.is(sumFloat32Slice(floats), 12)
+
+  t.throws(
+    // @ts-expect-error
+    () => sumFloat32Slice(new Float64Array([1, 2])),
+    null,
+    'Expect value to be Float32Array, but received Float64Array',
+  )
+})
+
 test('async', async (t) => {
   const bufPromise = readFileAsync(join(__dirname, '../package.json'))
   await t.notThrowsAsync(bufPromise)
@@ -150,6 +715,100 @@ test('async', async (t) => {
   t.is(name, 'napi-examples')
 
   await t.throwsAsync(() => readFileAsync('some_nonexist_path.file'))
+
+  const input = Buffer.from('abc')
+  const reversed = await reverseBufferAsync(input)
+  t.is(reversed.toString(), 'cba')
+  t.is(input.toString(), 'abc')
+})
+
+test('await promise argument', async (t) => {
+  t.is(await asyncPlus100(Promise.resolve(1)), 101)
+  t.is(
+    await asyncPlus100(new Promise((resolve) => setTimeout(() => resolve(2), 50))),
+    102,
+  )
+
+  const reason = new Error('rejected')
+  const error = await t.throwsAsync(() => asyncPlus100(Promise.reject(reason)))
+  t.is(error, reason)
+
+  await t.throwsAsync(() => asyncPlus100(Promise.resolve('1' as any)), {
+    message: 'Failed to convert napi value into rust type `u32`',
+  })
+  t.throws(() => asyncPlus100(1 as any), {
+    instanceOf: TypeError,
+    message: 'Expect value to be a Promise',
+  })
+})
+
+test('promise settled more than once by overridden then', async (t) => {
+  const promise = Promise.resolve(1)
+  promise.then = ((onFulfilled: (value: number) => void, onRejected: (reason: unknown) => void) => {
+    onFulfilled(1)
+    onFulfilled(2)
+    onRejected(new Error('rejected'))
+  }) as any
+  t.is(await asyncPlus100(promise), 101)
+})
+
+GcTest('rejection dropped on other threads is collectable', async (t) => {
+  let reason: object | undefined = { payload: Buffer.alloc(1024 * 1024) }
+  const weakRef = new WeakRef(reason)
+  t.is(await plus100OrZero(Promise.reject(reason)), 0)
+  reason = undefined
+  // the dropped exceptions are released when the next async fn settles
+  t.is(await plus100OrZero(Promise.resolve(1)), 101)
+  await new Promise((resolve) => setTimeout(resolve, 0))
+  global.gc!()
+  t.is(weakRef.deref(), undefined)
+})
+
+test('date', (t) => {
+  const date = new Date('2021-12-01T00:00:00.123Z')
+  t.is(dateToNumber(date), date.valueOf())
+  t.is(addOneSecond(date).valueOf(), date.valueOf() + 1000)
+  t.is(addOneDay(date).valueOf(), date.valueOf() + 24 * 3600 * 1000)
+  t.is(toIsoString(date), '2021-12-01T00:00:00.123')
+  t.is(createDate(0).valueOf(), 0)
+
+  t.throws(() => dateToNumber(new Date('not a date')), {
+    instanceOf: RangeError,
+    message: 'Invalid Date, the time value is NaN',
+  })
+  t.throws(
+    // @ts-expect-error
+    () => dateToNumber(0),
+    { instanceOf: TypeError, message: 'Expect value to be a Date' },
+  )
+  t.throws(() => createDate(8.64e15 + 1), {
+    instanceOf: RangeError,
+    message: 'Time value 8640000000000001 is out of range of Date',
+  })
+})
+
+test('external', (t) => {
+  const db = openDb(16)
+  const cache = createCache(8)
+  t.is(typeof db, 'object')
+  t.is(dbRecordCount(db), 0)
+  t.is(cacheCapacity(cache), 8)
+  t.is(externalNumberPlusOne(createExternalNumber(41)), 42)
+
+  t.throws(
+    // @ts-expect-error
+    () => dbRecordCount(cache),
+    {
+      instanceOf: TypeError,
+      message:
+        'Expect value to be an External of rust type `napi_examples::external::Db`',
+    },
+  )
+  t.throws(
+    // @ts-expect-error
+    () => dbRecordCount({}),
+    { message: 'Expect value to be External, but received Object' },
+  )
 })
 
 test('either', (t) => {
@@ -196,8 +855,96 @@ MaybeTest('async task with abort controller', async (t) => {
   }
 })
 
+MaybeTest('async task with aborted signal', async (t) => {
+  const ctrl = new AbortController()
+  ctrl.abort()
+  await t.throwsAsync(withAbortController(1, 2, ctrl.signal), {
+    code: 'ABORT_ERR',
+    message: 'AbortError',
+  })
+})
+
+MaybeTest('abort signal shared by calls', async (t) => {
+  const ctrl = new AbortController()
+  let onabortCalled = 0
+  ctrl.signal.onabort = () => {
+    onabortCalled++
+  }
+  const promises = [
+    delayWithSignal(10000, ctrl.signal),
+    delayWithSignal(10000, ctrl.signal),
+    withAbortController(1, 2, ctrl.signal),
+  ]
+  setTimeout(() => ctrl.abort(), 10)
+  const results = await Promise.allSettled(promises)
+  t.deepEqual(
+    results.map((result) => result.status === 'rejected' && result.reason.code),
+    ['ABORT_ERR', 'ABORT_ERR', 'ABORT_ERR'],
+  )
+  t.is(onabortCalled, 1)
+})
+
 MaybeTest('abort resolved task', async (t) => {
   const ctrl = new AbortController()
   await withAbortController(1, 2, ctrl.signal).then(() => ctrl.abort())
   t.pass('should not throw')
 })
+
+MaybeTest('abort async fn', async (t) => {
+  t.is(await delayWithSignal(10, new AbortController().signal), 10)
+
+  const ctrl = new AbortController()
+  const promise = delayWithSignal(10000, ctrl.signal)
+  setTimeout(() => ctrl.abort(), 10)
+  const err = await t.throwsAsync(promise, {
+    code: 'ABORT_ERR',
+    message: 'AbortError',
+  })
+  t.is(err.name, 'AbortError')
+
+  const aborted = new AbortController()
+  aborted.abort()
+  await t.throwsAsync(delayWithSignal(10000, aborted.signal), {
+    code: 'ABORT_ERR',
+  })
+})
+
+test('load module in multiple workers', async (t) => {
+  const workerScript = `
+    const { parentPort, workerData } = require('worker_threads')
+    const { Animal, Dog, ClassWithFactory, Kind, add } = require(workerData)
+    const dog = new Dog('Rex')
+    parentPort.postMessage({
+      sum: add(1, 2),
+      kind: Kind.Cat,
+      dogName: dog.name,
+      isAnimal: dog instanceof Animal,
+      fromFactory: ClassWithFactory.withName('worker').name,
+    })
+  `
+  const results = await Promise.all(
+    Array.from({ length: 3 }).map(
+      () =>
+        new Promise((resolve, reject) => {
+          const worker = new Worker(workerScript, {
+            eval: true,
+            workerData: join(__dirname, '..', 'index.node'),
+          })
+          worker.once('message', resolve)
+          worker.once('error', reject)
+        }),
+    ),
+  )
+  for (const result of results) {
+    t.deepEqual(result, {
+      sum: 3,
+      kind: Kind.Cat,
+      dogName: 'Rex',
+      isAnimal: true,
+      fromFactory: 'worker',
+    })
+  }
+  // the module still works in the main thread after the workers exit
+  t.true(ClassWithFactory.withName('main') instanceof ClassWithFactory)
+  t.is(await withoutAbortController(1, 2), 3)
+})
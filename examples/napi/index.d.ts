@@ -1,50 +1,194 @@
+declare const externalObjectBrand: unique symbol
+export interface ExternalObject<T extends string> {
+  readonly [externalObjectBrand]: T
+}
 export function getWords(): Array<string>
 export function getNums(): Array<number>
 export function sumNums(nums: Array<number>): number
 export function readFileAsync(path: string): Promise<Buffer>
+/** Reverse the bytes of `buf` in a new `Buffer` */
+export function reverseBufferAsync(buf: Buffer): Promise<Buffer>
+export function asyncPlus100(p: Promise<number>): Promise<number>
+export function plus100OrZero(p: Promise<number>): Promise<number>
+export function delayWithSignal(ms: number, signal: AbortSignal): Promise<number>
+export function bigintAdd(a: BigInt, b: BigInt): BigInt
+export function createBigInt(): BigInt
+export function u64MaxPlus(a: BigInt): BigInt
+export function i128Negate(a: BigInt): BigInt
 export function getCwd(callback: (arg0: string) => void): void
+/** napi = { version = 2, features = ["serde-json"] } */
 export function readFile(callback: (arg0: Error | undefined, arg1: string | null) => void): void
+/** The exception thrown by `callback` is rethrown unchanged */
+export function callWithOne(callback: (arg0: number) => number): number
+export function callFunction(add: (arg0: number, arg1: number) => number): number
+export function callWithThis(getName: () => string, target: object): string
+export function createInstance(constructor: (arg0: string) => unknown, name: string): object
+export function getAnimalName(animal: Animal): string
+export function dateToNumber(input: Date): number
+export function addOneSecond(time: Date): Date
+export function addOneDay(time: Date): Date
+export function toIsoString(time: Date): string
+export function createDate(timestamp: number): Date
 export function eitherStringOrNumber(input: string | number): number
 export function returnEither(input: number): string | number
 export function either3(input: string | number | boolean): number
-interface Obj {
+export interface Obj {
   v: string | number
 }
 export function either4(input: string | number | boolean | Obj): number
+/** default enum values are continuos i32s start from 0 */
 export enum Kind { Dog = 0, Cat = 1, Duck = 2 }
+/** You could break the step and for an new continuous value. */
 export enum CustomNumEnum { One = 1, Two = 2, Three = 3, Four = 4, Six = 6, Eight = 8, Nine = 9, Ten = 10 }
 export function enumToI32(e: CustomNumEnum): number
+/** string enums are mapped to the string literals of their variants */
+export type Direction = 'Up' | 'Down' | 'left' | 'right'
+export function oppositeDirection(d: Direction): Direction
+/** data-carrying enums are mapped to objects discriminated by the `type` field */
+export type Shape = { type: 'Circle', radius: number } | { type: 'Rect', width: number, height: number } | { type: 'Square', side: number, label?: string } | { type: 'Labeled', field0: string } | { type: 'Empty' }
+export function shapeArea(shape: Shape): number
+export function scaleShape(shape: Shape, factor: number): Shape
 export function throwError(): void
-export function mapOption(val: number | null): number | null
+/** Thrown when the input is invalid */
+export class ValidationError extends Error {}
+export function validatePort(port: number): number
+export function readConfig(path: string): string
+export function panicWithMessage(message: string): number
+export function panicInAsync(message: string): Promise<number>
+export function resumePanic(message: string): number
+export function addUnchecked(a: number, b: number): number
+export function openDb(capacity: number): ExternalObject<'Db'>
+export function dbRecordCount(db: ExternalObject<'Db'>): number
+export function createCache(capacity: number): ExternalObject<'Cache'>
+export function cacheCapacity(cache: ExternalObject<'Cache'>): number
+export function createExternalNumber(value: number): ExternalObject<'u32'>
+export function externalNumberPlusOne(value: ExternalObject<'u32'>): number
+export function mapOption(val?: number | null): number | null
+/**
+ * Add two numbers
+ *
+ * @param a the left-hand side
+ * @param b the right-hand side, which could be any unsigned 32-bit integer
+ */
 export function add(a: number, b: number): number
 export function fibonacci(n: number): number
+export function pow(base: number, exponent?: number): number
+export function sumAll(first: number, ...rest: number[]): number
+export function wrappingAddU8(a: number, b: number): number
+export function sumSmall(a: number, b: number, c: number): number
+export function toF32(value: number): number
+export function checkedIndex(len: number, index: number): number | null
+export function usizeMax(): number
+export function strictU8(value: number): number
 export function listObjKeys(obj: object): Array<string>
 export function createObj(): object
-interface PackageJson {
+/** Options of the task runner */
+export interface Config {
+  name: string
+  /** How many times the task is retried before failing */
+  retries?: number
+  logLevel?: string
+  timeout?: number
+}
+export function normalizeConfig(config: Config): Config
+export function configTimeout(config: Config): number
+export function greetThis(this: object, greeting: string): string
+export interface PackageJson {
   name: string
   version: string
-  dependencies: Record<string, any> | null
-  devDependencies: Record<string, any> | null
+  dependencies?: Record<string, any>
+  devDependencies?: Record<string, any>
 }
 export function readPackageJson(): PackageJson
 export function getPackageJsonName(packageJson: PackageJson): string
 export function contains(source: string, target: string): boolean
-export function concatStr(mutS: string): string
+export function concatStr(s: string): string
 export function concatUtf16(s: string): string
 export function concatLatin1(s: string): string
+export function joinWords(separator: string, ...words: string[]): string
 export function withoutAbortController(a: number, b: number): Promise<number>
 export function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
+export function callThreadsafeFunction(callback: (err: Error | null, value: number) => void): void
+export function threadsafeFunctionThrowError(cb: (err: Error | null, value: boolean) => void): void
+export function threadsafeFunctionFatalMode(cb: (value: boolean) => void): void
 export function getBuffer(): Buffer
+export function appendBuffer(buf: Buffer): Buffer
+export function bufferPassThrough(buf: Buffer): Buffer
+export function copyBuffer(buf: Buffer): Buffer
+export function fillBuffer(buf: Buffer, value: number): Buffer
+export function convertU32Array(input: Uint32Array): Array<number>
+export function createExternalTypedArray(): Uint32Array
+export function mutateTypedArray(input: Float32Array): void
+export function sumFloat64Array(input: Float64Array): number
+export function doubleInt32Array(input: Int32Array): Int32Array
+export function hashBytes(data: Uint8Array): number
+export function reverseBytes(data: Uint8Array): void
+export function scaleFloat32Slice(data: Float32Array, factor: number): void
+export function sumFloat32Slice(data: Float32Array): number
+/** Keeps the listener across calls by the persistent reference */
+export class Emitter {
+  /** Count of the emitted values */
+  readonly emitted: number
+  constructor(listener: (arg0: number) => number)
+  emit(value: number): number
+}
+/**
+ * `constructor` option for `struct` requires all fields to be public,
+ * otherwise tag impl fn as constructor
+ * #[napi(constructor)]
+ */
 export class Animal {
+  /** Kind of animal */
   readonly kind: Kind
+  /** Name of the animals created by `Animal.withKind` */
+  static readonly DEFAULT_NAME: string
+  /**
+   * Create a new animal
+   *
+   * @param kind kind of the animal
+   * @param name name of the animal
+   */
   constructor(kind: Kind, name: string)
   static withKind(kind: Kind): Animal
   get name(): string
   set name(name: string)
+  /** This is a doc comment for a method, returns the kind and name of the animal */
   whoami(): string
   static getDogKind(): Kind
+  /** Number of the animal kinds */
+  static get kindCount(): number
+}
+/** Dogs are animals, all the methods of `Animal` are inherited */
+export class Dog extends Animal {
+  readonly tricks: number
+  constructor(name: string)
+  learnTrick(): number
+  rename(name: string): void
+}
+/**
+ * Instances passed back to Rust are borrow checked in runtime,
+ * conflicting borrows in the callbacks throw instead of aliasing.
+ */
+export class Counter {
+  count: number
+  constructor(count: number)
+  increaseWith(callback: () => void): number
+  readWith(callback: () => void): number
+  addFrom(other: Counter): number
+}
+/** Records the class it's constructed with, which differs when it's extended in JavaScript */
+export class Base {
+  readonly constructedBy: string
+  readonly isSubclass: boolean
+  constructor()
 }
 export class ClassWithFactory {
   name: string
   static withName(name: string): ClassWithFactory
 }
+export class Fragile {
+  
+  constructor(value: number)
+  get value(): number
+  set value(value: number)
+}
@@ -14,3 +14,29 @@ async fn read_file_async(path: String) -> Result<Buffer> {
     })
     .await
 }
+
+/// Reverse the bytes of `buf` in a new `Buffer`
+#[napi]
+async fn reverse_buffer_async(buf: Buffer) -> Buffer {
+  let mut data = Vec::<u8>::from(buf);
+  data.reverse();
+  data.into()
+}
+
+#[napi]
+async fn async_plus_100(p: Promise<u32>) -> Result<u32> {
+  let v = p.await?;
+  Ok(v + 100)
+}
+
+// the rejection is dropped on the tokio thread
+#[napi]
+async fn plus_100_or_zero(p: Promise<u32>) -> u32 {
+  p.await.map_or(0, |v| v + 100)
+}
+
+#[napi]
+async fn delay_with_signal(ms: u32, _signal: AbortSignal) -> Result<u32> {
+  tokio::time::sleep(std::time::Duration::from_millis(ms as u64)).await;
+  Ok(ms)
+}
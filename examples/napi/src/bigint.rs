@@ -0,0 +1,33 @@
+use napi::bindgen_prelude::*;
+
+#[napi]
+fn bigint_add(a: BigInt, b: BigInt) -> Result<i128> {
+  let out_of_range = || {
+    Error::new(Status::InvalidArg, "BigInt is out of range".to_owned())
+      .with_kind(ErrorKind::RangeError)
+  };
+  let (a, a_lossless) = a.get_i128();
+  let (b, b_lossless) = b.get_i128();
+  if !a_lossless || !b_lossless {
+    return Err(out_of_range());
+  }
+  a.checked_add(b).ok_or_else(out_of_range)
+}
+
+#[napi]
+fn create_big_int() -> BigInt {
+  BigInt {
+    sign_bit: true,
+    words: vec![100, 200],
+  }
+}
+
+#[napi]
+fn u64_max_plus(a: u64) -> u128 {
+  u64::MAX as u128 + a as u128
+}
+
+#[napi]
+fn i128_negate(a: i128) -> i128 {
+  -a
+}
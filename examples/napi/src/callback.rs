@@ -20,3 +20,50 @@ fn read_file_content() -> Result<String> {
   // serde_json::from_str(&s)?;
   Ok("hello world".to_string())
 }
+
+/// The exception thrown by `callback` is rethrown unchanged
+#[napi]
+fn call_with_one<T: Fn(u32) -> Result<u32>>(callback: T) -> Result<u32> {
+  callback(1).map(|v| v + 1)
+}
+
+#[napi]
+fn call_function(add: Function<(u32, u32), u32>) -> Result<u32> {
+  add.call((), (1, 2))
+}
+
+#[napi]
+fn call_with_this(get_name: Function<(), String>, target: Object) -> Result<String> {
+  get_name.call(target, ())
+}
+
+#[napi]
+fn create_instance(constructor: Function<(String,)>, name: String) -> Result<Object> {
+  constructor.new_instance((name,))
+}
+
+/// Keeps the listener across calls by the persistent reference
+#[napi]
+pub struct Emitter {
+  /// Count of the emitted values
+  #[napi(readonly)]
+  pub emitted: u32,
+  listener: FunctionRef<(u32,), u32>,
+}
+
+#[napi]
+impl Emitter {
+  #[napi(constructor)]
+  pub fn new(listener: Function<(u32,), u32>) -> Result<Self> {
+    Ok(Emitter {
+      emitted: 0,
+      listener: listener.create_ref()?,
+    })
+  }
+
+  #[napi]
+  pub fn emit(&mut self, value: u32) -> Result<u32> {
+    self.emitted += 1;
+    self.listener.borrow_back()?.call((), (value,))
+  }
+}
@@ -7,6 +7,7 @@ use crate::r#enum::Kind;
 /// #[napi(constructor)]
 #[napi]
 pub struct Animal {
+  /// Kind of animal
   #[napi(readonly)]
   pub kind: Kind,
   name: String,
@@ -14,6 +15,16 @@ pub struct Animal {
 
 #[napi]
 impl Animal {
+  /// Name of the animals created by `Animal.withKind`
+  #[napi]
+  pub const DEFAULT_NAME: &'static str = "Default";
+
+  /// Create a new animal
+  ///
+  /// # Arguments
+  ///
+  /// * `kind` - kind of the animal
+  /// * `name` - name of the animal
   #[napi(constructor)]
   pub fn new(kind: Kind, name: String) -> Self {
     Animal { kind, name }
@@ -23,7 +34,7 @@ impl Animal {
   pub fn with_kind(kind: Kind) -> Self {
     Animal {
       kind,
-      name: "Default".to_owned(),
+      name: Animal::DEFAULT_NAME.to_owned(),
     }
   }
 
@@ -37,6 +48,7 @@ impl Animal {
     self.name = name;
   }
 
+  /// This is a doc comment for a method, returns the kind and name of the animal
   #[napi]
   pub fn whoami(&self) -> String {
     match self.kind {
@@ -52,4 +64,94 @@ impl Animal {
   pub fn get_dog_kind() -> Kind {
     Kind::Dog
   }
+
+  /// Number of the animal kinds
+  #[napi(getter)]
+  pub fn kind_count() -> u32 {
+    3
+  }
+}
+
+/// Dogs are animals, all the methods of `Animal` are inherited
+#[napi(extends = Animal)]
+pub struct Dog {
+  animal: Animal,
+  #[napi(readonly)]
+  pub tricks: u32,
+}
+
+#[napi]
+impl Dog {
+  #[napi(constructor)]
+  pub fn new(name: String) -> Self {
+    Dog {
+      animal: Animal::new(Kind::Dog, name),
+      tricks: 0,
+    }
+  }
+
+  #[napi]
+  pub fn learn_trick(&mut self) -> u32 {
+    self.tricks += 1;
+    self.tricks
+  }
+
+  #[napi]
+  pub fn rename(&mut self, name: String) {
+    self.animal.set_name(name);
+  }
+}
+
+#[napi]
+fn get_animal_name(animal: &Animal) -> String {
+  animal.get_name().to_owned()
+}
+
+/// Instances passed back to Rust are borrow checked in runtime,
+/// conflicting borrows in the callbacks throw instead of aliasing.
+#[napi(constructor)]
+pub struct Counter {
+  pub count: u32,
+}
+
+#[napi]
+impl Counter {
+  #[napi]
+  pub fn increase_with<T: Fn() -> Result<()>>(&mut self, callback: T) -> Result<u32> {
+    self.count += 1;
+    callback()?;
+    Ok(self.count)
+  }
+
+  #[napi]
+  pub fn read_with<T: Fn() -> Result<()>>(&self, callback: T) -> Result<u32> {
+    callback()?;
+    Ok(self.count)
+  }
+
+  #[napi]
+  pub fn add_from(&mut self, other: &Counter) -> u32 {
+    self.count += other.count;
+    self.count
+  }
+}
+
+/// Records the class it's constructed with, which differs when it's extended in JavaScript
+#[napi]
+pub struct Base {
+  #[napi(readonly)]
+  pub constructed_by: String,
+  #[napi(readonly)]
+  pub is_subclass: bool,
+}
+
+#[napi]
+impl Base {
+  #[napi(constructor)]
+  pub fn new(new_target: NewTarget) -> Result<Self> {
+    Ok(Base {
+      constructed_by: new_target.name()?.unwrap_or_default(),
+      is_subclass: !new_target.is_class::<Base>()?,
+    })
+  }
 }
@@ -0,0 +1,29 @@
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDateTime, Utc};
+use napi::bindgen_prelude::*;
+
+#[napi]
+fn date_to_number(input: Date) -> f64 {
+  input.timestamp()
+}
+
+#[napi]
+fn add_one_second(time: SystemTime) -> SystemTime {
+  time + Duration::from_secs(1)
+}
+
+#[napi]
+fn add_one_day(time: DateTime<Utc>) -> DateTime<Utc> {
+  time + ChronoDuration::days(1)
+}
+
+#[napi]
+fn to_iso_string(time: NaiveDateTime) -> String {
+  time.format("%Y-%m-%dT%H:%M:%S%.3f").to_string()
+}
+
+#[napi]
+fn create_date(timestamp: f64) -> Result<Date> {
+  Date::from_timestamp(timestamp)
+}
@@ -25,3 +25,63 @@ pub enum CustomNumEnum {
 fn enum_to_i32(e: CustomNumEnum) -> i32 {
   e as i32
 }
+
+/// string enums are mapped to the string literals of their variants
+#[napi(string_enum)]
+pub enum Direction {
+  Up,
+  Down,
+  #[napi(js_name = "left")]
+  Left,
+  #[napi(js_name = "right")]
+  Right,
+}
+
+#[napi]
+fn opposite_direction(d: Direction) -> Direction {
+  match d {
+    Direction::Up => Direction::Down,
+    Direction::Down => Direction::Up,
+    Direction::Left => Direction::Right,
+    Direction::Right => Direction::Left,
+  }
+}
+
+/// data-carrying enums are mapped to objects discriminated by the `type` field
+#[napi]
+pub enum Shape {
+  Circle { radius: f64 },
+  Rect { width: f64, height: f64 },
+  Square { side: f64, label: Option<String> },
+  Labeled(String),
+  Empty,
+}
+
+#[napi]
+fn shape_area(shape: Shape) -> f64 {
+  match shape {
+    Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+    Shape::Rect { width, height } => width * height,
+    Shape::Square { side, .. } => side * side,
+    Shape::Labeled(_) | Shape::Empty => 0.0,
+  }
+}
+
+#[napi]
+fn scale_shape(shape: Shape, factor: f64) -> Shape {
+  match shape {
+    Shape::Circle { radius } => Shape::Circle {
+      radius: radius * factor,
+    },
+    Shape::Rect { width, height } => Shape::Rect {
+      width: width * factor,
+      height: height * factor,
+    },
+    Shape::Square { side, label } => Shape::Square {
+      side: side * factor,
+      label,
+    },
+    Shape::Labeled(label) => Shape::Labeled(label),
+    Shape::Empty => Shape::Empty,
+  }
+}
@@ -1,6 +1,103 @@
+use std::fmt;
+
 use napi::bindgen_prelude::*;
 
 #[napi]
 fn throw_error() -> Result<()> {
   Err(Error::new(Status::InvalidArg, "Manual Error".to_owned()))
 }
+
+/// Thrown when the input is invalid
+#[napi(error)]
+pub struct ValidationError;
+
+#[napi]
+fn validate_port(port: u32) -> Result<u32> {
+  if port > 65535 {
+    return Err(
+      Error::new(Status::InvalidArg, format!("Port {} is out of range", port))
+        .with_code("ERR_OUT_OF_RANGE")
+        .with_kind(ErrorKind::RangeError),
+    );
+  }
+  if port < 1024 {
+    return Err(
+      Error::new(Status::InvalidArg, format!("Port {} is reserved", port))
+        .with_code("ERR_RESERVED_PORT")
+        .with_kind(ValidationError)
+        .with_property("port", port),
+    );
+  }
+
+  Ok(port)
+}
+
+#[derive(Debug)]
+struct ConfigError {
+  path: String,
+  source: std::io::Error,
+}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Failed to read config `{}`", self.path)
+  }
+}
+
+impl std::error::Error for ConfigError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    Some(&self.source)
+  }
+}
+
+#[napi]
+fn read_config(path: String) -> Result<String> {
+  std::fs::read_to_string(&path)
+    .map_err(|source| Error::from_error(&ConfigError { path, source }).with_code("ERR_CONFIG"))
+}
+
+#[napi]
+fn panic_with_message(message: String) -> u32 {
+  panic!("{}", message)
+}
+
+#[napi]
+async fn panic_in_async(message: String) -> Result<u32> {
+  panic!("{}", message)
+}
+
+#[napi]
+fn resume_panic(message: String) -> u32 {
+  std::panic::resume_unwind(Box::new(message))
+}
+
+#[napi]
+pub struct Fragile {
+  value: u32,
+}
+
+#[napi]
+impl Fragile {
+  #[napi(constructor)]
+  pub fn new(value: u32) -> Self {
+    assert!(value != 0, "value of Fragile must not be 0");
+    Fragile { value }
+  }
+
+  #[napi(getter)]
+  pub fn get_value(&self) -> u32 {
+    assert!(self.value != 13, "value of Fragile is 13");
+    self.value
+  }
+
+  #[napi(setter)]
+  pub fn set_value(&mut self, value: u32) {
+    assert!(value != 0, "value of Fragile must not be 0");
+    self.value = value;
+  }
+}
+
+#[napi(no_catch_unwind)]
+fn add_unchecked(a: u32, b: u32) -> u32 {
+  a + b
+}
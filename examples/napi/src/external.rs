@@ -0,0 +1,44 @@
+use napi::bindgen_prelude::*;
+
+pub struct Db {
+  records: Vec<String>,
+}
+
+pub struct Cache {
+  capacity: u32,
+}
+
+#[napi]
+fn open_db(capacity: u32) -> External<Db> {
+  External::new_with_size_hint(
+    Db {
+      records: Vec::with_capacity(capacity as usize),
+    },
+    capacity as usize * 64,
+  )
+}
+
+#[napi]
+fn db_record_count(db: &External<Db>) -> u32 {
+  db.records.len() as u32
+}
+
+#[napi]
+fn create_cache(capacity: u32) -> External<Cache> {
+  External::new(Cache { capacity })
+}
+
+#[napi]
+fn cache_capacity(cache: &External<Cache>) -> u32 {
+  cache.capacity
+}
+
+#[napi]
+fn create_external_number(value: u32) -> External<u32> {
+  External::new(value)
+}
+
+#[napi]
+fn external_number_plus_one(value: External<u32>) -> u32 {
+  *value + 1
+}
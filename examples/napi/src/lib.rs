@@ -5,16 +5,20 @@ extern crate serde_derive;
 
 mod array;
 mod r#async;
+mod bigint;
 mod callback;
 mod class;
 mod class_factory;
+mod date;
 mod either;
 mod r#enum;
 mod error;
+mod external;
 mod nullable;
 mod number;
 mod object;
 mod serde;
 mod string;
 mod task;
+mod threadsafe_function;
 mod typed_array;
@@ -1,5 +1,12 @@
 use napi::bindgen_prelude::*;
 
+/// Add two numbers
+///
+/// # Arguments
+///
+/// * `a` - the left-hand side
+/// * `b` - the right-hand side,
+///   which could be any unsigned 32-bit integer
 #[napi]
 fn add(a: u32, b: u32) -> u32 {
   a + b
@@ -12,3 +19,47 @@ fn fibonacci(n: u32) -> u32 {
     _ => fibonacci(n - 1) + fibonacci(n - 2),
   }
 }
+
+#[napi]
+fn pow(base: u32, #[napi(default = 2)] exponent: u32) -> u32 {
+  base.pow(exponent)
+}
+
+#[napi]
+fn sum_all(first: u32, rest: Rest<u32>) -> u32 {
+  first + rest.iter().sum::<u32>()
+}
+
+#[napi]
+fn wrapping_add_u8(a: u8, b: u8) -> u8 {
+  a.wrapping_add(b)
+}
+
+#[napi]
+fn sum_small(a: i8, b: u16, c: i16) -> i32 {
+  a as i32 + b as i32 + c as i32
+}
+
+#[napi]
+fn to_f32(value: f32) -> f32 {
+  value
+}
+
+#[napi]
+fn checked_index(len: usize, #[napi(strict)] index: usize) -> Option<usize> {
+  if index < len {
+    Some(index)
+  } else {
+    None
+  }
+}
+
+#[napi]
+fn usize_max() -> usize {
+  usize::MAX
+}
+
+#[napi(strict)]
+fn strict_u8(value: u8) -> u8 {
+  value
+}
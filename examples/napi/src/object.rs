@@ -12,3 +12,31 @@ fn create_obj(env: Env) -> Object {
 
   obj
 }
+
+/// Options of the task runner
+#[napi(object)]
+pub struct Config {
+  pub name: String,
+  /// How many times the task is retried before failing
+  #[napi(default = 3)]
+  pub retries: u32,
+  #[napi(default = String::from("info"))]
+  pub log_level: String,
+  pub timeout: Option<u32>,
+}
+
+#[napi]
+fn normalize_config(config: Config) -> Config {
+  config
+}
+
+#[napi]
+fn config_timeout(config: Config) -> u32 {
+  config.timeout.unwrap_or(1000)
+}
+
+#[napi]
+fn greet_this(this: This<Object>, greeting: String) -> Result<String> {
+  let name: Option<String> = this.get("name")?;
+  Ok(format!("{}, {}", greeting, name.unwrap_or_default()))
+}
@@ -0,0 +1,41 @@
+use std::thread;
+
+use napi::{
+  bindgen_prelude::*,
+  threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+
+#[napi]
+pub fn call_threadsafe_function(callback: ThreadsafeFunction<u32>) -> Result<()> {
+  for n in 0..100 {
+    let tsfn = callback.clone();
+    thread::spawn(move || {
+      tsfn.call(Ok(n), ThreadsafeFunctionCallMode::NonBlocking);
+    });
+  }
+  Ok(())
+}
+
+#[napi]
+pub fn threadsafe_function_throw_error(cb: ThreadsafeFunction<bool>) -> Result<()> {
+  thread::spawn(move || {
+    cb.call(
+      Err(Error::new(
+        Status::GenericFailure,
+        "ThrowFromNative".to_owned(),
+      )),
+      ThreadsafeFunctionCallMode::Blocking,
+    );
+  });
+  Ok(())
+}
+
+#[napi]
+pub fn threadsafe_function_fatal_mode(
+  cb: ThreadsafeFunction<bool, ErrorStrategy::Fatal>,
+) -> Result<()> {
+  thread::spawn(move || {
+    cb.call(true, ThreadsafeFunctionCallMode::Blocking);
+  });
+  Ok(())
+}
@@ -4,3 +4,77 @@ use napi::bindgen_prelude::*;
 fn get_buffer() -> Buffer {
   String::from("Hello world").as_bytes().into()
 }
+
+#[napi]
+fn append_buffer(buf: Buffer) -> Buffer {
+  let mut buf = Vec::<u8>::from(buf);
+  buf.push(b'!');
+  buf.into()
+}
+
+#[napi]
+fn buffer_pass_through(buf: BufferRef) -> BufferRef {
+  buf
+}
+
+#[napi]
+fn copy_buffer(buf: BufferRef) -> Buffer {
+  buf.to_buffer()
+}
+
+#[napi]
+fn fill_buffer(mut buf: BufferRef, value: u32) -> BufferRef {
+  buf.fill(value as u8);
+  buf
+}
+
+#[napi]
+fn convert_u32_array(input: Uint32Array) -> Vec<u32> {
+  input.to_vec()
+}
+
+#[napi]
+fn create_external_typed_array() -> Uint32Array {
+  Uint32Array::new(vec![1, 2, 3, 4, 5])
+}
+
+#[napi]
+fn mutate_typed_array(mut input: Float32Array) {
+  for item in input.iter_mut() {
+    *item *= 2.0;
+  }
+}
+
+#[napi]
+fn sum_float64_array(input: Float64Array) -> f64 {
+  input.iter().sum()
+}
+
+#[napi]
+fn double_int32_array(input: Int32Array) -> Int32Array {
+  input.iter().map(|v| v * 2).collect::<Vec<i32>>().into()
+}
+
+#[napi]
+fn hash_bytes(data: &[u8]) -> u32 {
+  data.iter().fold(2166136261u32, |hash, b| {
+    (hash ^ *b as u32).wrapping_mul(16777619)
+  })
+}
+
+#[napi]
+fn reverse_bytes(data: &mut [u8]) {
+  data.reverse();
+}
+
+#[napi]
+fn scale_float32_slice(data: &mut [f32], factor: f64) {
+  for item in data.iter_mut() {
+    *item *= factor as f32;
+  }
+}
+
+#[napi]
+fn sum_float32_slice(data: &[f32]) -> f64 {
+  data.iter().map(|v| *v as f64).sum()
+}